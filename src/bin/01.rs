@@ -1,64 +1,38 @@
+use advent_of_code::modular_counter::{ModularCounter, Tally};
+use advent_of_code::parse::{self, report, ParseError};
+
 advent_of_code::solution!(1);
 
 struct Safe {
-    position: usize,
-    instructions: Vec<isize>,
-}
-
-fn wrap_with_counts(position: i64, input: i64, min: i64, max: i64) -> (i64, i64) {
-    let modulus = max - min;
-    let new_position = position + input;
-
-    let final_position = ((new_position % modulus + modulus) % modulus) + min;
-    let overwraps = if input > 0 {
-        new_position / modulus - position / modulus
-    } else {
-        (position - 1).div_euclid(modulus) - (new_position - 1).div_euclid(modulus)
-    };
-
-    (final_position, overwraps)
+    dial: ModularCounter,
+    instructions: Vec<i64>,
 }
 
 impl Safe {
-    fn from_text(starting_position: usize, text: &str) -> Self {
-        let instructions = text.lines().map(|line| {
-            let (dir, amount) = line.split_at(1);
-            let multiplier = if dir == "L" { -1 } else { 1 };
-            amount.parse::<isize>().unwrap_or(0) * multiplier
-        }).collect();
-
-        Safe { position: starting_position, instructions }
-    }
+    fn from_text(starting_position: i64, text: &str) -> Result<Self, ParseError> {
+        let instructions = parse::lines(text, |line| {
+            let multiplier = match line.text.chars().next() {
+                Some('L') => -1,
+                Some('R') => 1,
+                _ => return Err(line.error("expected a rotation starting with `L` or `R`")),
+            };
+            Ok(line.slice(1, line.text.len()).parse::<i64>()? * multiplier)
+        })?;
 
-    fn count_ending_positions(&mut self, target: usize) -> Option<u64> {
-        let mut matches = 0;
-        for &amount in &self.instructions {
-            let (new_position, _) = wrap_with_counts(self.position as i64, amount as i64, 0, 100);
-            self.position = new_position as usize;
-            if self.position == target {
-                matches += 1;
-            }
-        }
-        Some(matches)
+        Ok(Safe { dial: ModularCounter::new(100, starting_position), instructions })
     }
 
-    fn count_overflows(&mut self) -> Option<u64> {
-        let mut visits = 0;
-        for &amount in &self.instructions {
-            let (new_position, overwraps) = wrap_with_counts(self.position as i64, amount as i64, 0, 100);
-            self.position = new_position as usize;
-            visits += overwraps as u64;
-        }
-        Some(visits)
+    fn turn_dial(&mut self, target: i64) -> Tally {
+        self.dial.apply_all(self.instructions.iter().copied(), target)
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    Safe::from_text(50, input).count_ending_positions(0)
+    Some(report(Safe::from_text(50, input))?.turn_dial(0).landings)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    Safe::from_text(50, input).count_overflows()
+    Some(report(Safe::from_text(50, input))?.turn_dial(0).crossings)
 }
 
 #[cfg(test)]
@@ -77,9 +51,23 @@ mod tests {
         assert_eq!(result, Some(6));
     }
 
+    #[test]
+    fn test_invalid_rotation() {
+        let error = Safe::from_text(50, "L10\nR5\nX3").err().unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = Safe::from_text(50, "L1O").err().unwrap();
+        assert_eq!((error.line, error.column), (1, 2));
+    }
+
     #[test]
     fn test_overwrap_with_counts() {
-        assert_eq!(wrap_with_counts(14, -82, 0, 100), (32, 1));
-        assert_eq!(wrap_with_counts(80, -687, 0, 100), (93, 7));
+        let mut safe = Safe::from_text(14, "L82").unwrap();
+        assert_eq!(safe.turn_dial(0).crossings, 1);
+        assert_eq!(safe.dial.position(), 32);
+
+        let mut safe = Safe::from_text(80, "L687").unwrap();
+        assert_eq!(safe.turn_dial(0).crossings, 7);
+        assert_eq!(safe.dial.position(), 93);
     }
 }
@@ -1,80 +1,64 @@
-use itertools::Itertools;
+use advent_of_code::parse::{report, ParseError, Span};
+use advent_of_code::repeated_digits::{sum_periodic, sum_repeated};
 
 advent_of_code::solution!(2);
 
 struct ProductRanges {
+    /// The ranges as listed. They are not merged: an id in two overlapping ranges counts twice.
     ranges: Vec<(u64, u64)>,
 }
 
 impl ProductRanges {
-    fn from_text(text: &str) -> Self {
-        let ranges = text.lines().flat_map(|line| {
-            line.split(',').map(|range| {
-                let parts = range.split('-').collect::<Vec<&str>>();
-                (
-                    parts[0].parse::<u64>().unwrap_or(0),
-                    parts[1].parse::<u64>().unwrap_or(0),
-                )
-            }).collect::<Vec<(u64, u64)>>()
-        }).collect();
-
-        ProductRanges { ranges }
+    fn from_text(text: &str) -> Result<Self, ParseError> {
+        let ranges = Span::new(text)
+            .split(&[',', '\n'])
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| range.range::<u64>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ProductRanges { ranges })
     }
 
-    fn is_doubled(text: &str) -> bool {
-        let len = text.len();
-        let parts = text.split_at(len / 2);
-
-        parts.0 == parts.1
-    }
+    /// Sums the ids that are some sequence of digits repeated exactly twice.
+    fn sum_invalid_ids(&self) -> Option<u64> {
+        let sum: u128 = self
+            .ranges
+            .iter()
+            .copied()
+            .flat_map(|(start, end)| {
+                (1..=10).map(move |block_len| sum_repeated(block_len, 2, start, end))
+            })
+            .sum();
 
-    fn is_repeated(text: &str) -> bool {
-        (1..=text.len() / 2).any(|chunk_size| {
-            text.len().is_multiple_of(chunk_size) && {
-                let pattern = &text[..chunk_size];
-                text.chars()
-                    .chunks(chunk_size)
-                    .into_iter()
-                    .all(|chunk| chunk.collect::<String>() == pattern)
-            }
-        })
+        u64::try_from(sum).ok()
     }
 
-    fn find_matching_ids<F>(&self, predicate: F) -> Vec<u64>
-    where
-        F: Fn(&str) -> bool,
-    {
-        self.ranges
+    /// Sums the ids that are some sequence of digits repeated at least twice.
+    fn sum_repeats(&self) -> Option<u64> {
+        let sum: u128 = self
+            .ranges
             .iter()
-            .flat_map(|(start, end)| *start..=*end)
-            .filter(|id| predicate(&id.to_string()))
-            .collect()
-    }
+            .copied()
+            .map(|(start, end)| sum_periodic(start, end))
+            .sum();
 
-    fn find_invalid_ids(&self) -> Vec<u64> {
-        self.find_matching_ids(Self::is_doubled)
-    }
-
-    fn find_repeats(&self) -> Vec<u64> {
-        self.find_matching_ids(Self::is_repeated)
+        u64::try_from(sum).ok()
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let sum = ProductRanges::from_text(input).find_invalid_ids().iter().sum::<u64>();
-
-    Some(sum)
+    report(ProductRanges::from_text(input))?.sum_invalid_ids()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let sum = ProductRanges::from_text(input).find_repeats().iter().sum::<u64>();
-
-    Some(sum)
+    report(ProductRanges::from_text(input))?.sum_repeats()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::repeated_digits::{is_periodic, is_repeated};
 
     #[test]
     fn test_part_one() {
@@ -88,32 +72,44 @@ mod tests {
         assert_eq!(result, Some(4174379265));
     }
 
+    #[test]
+    fn test_overlapping_ranges() {
+        assert_eq!(part_one("11-22,11-22"), Some(66));
+        assert_eq!(part_two("95-115,99-111"), Some(99 + 111 + 99 + 111));
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        assert_eq!(part_one("1-18446744073709551615"), None);
+        assert_eq!(part_two("1-18446744073709551615"), None);
+    }
+
     #[test]
     fn test_is_doubled() {
-        assert_eq!(ProductRanges::is_doubled("11"), true);
-        assert_eq!(ProductRanges::is_doubled("22"), true);
-        assert_eq!(ProductRanges::is_doubled("12"), false);
-        assert_eq!(ProductRanges::is_doubled("1010"), true);
-        assert_eq!(ProductRanges::is_doubled("1188511885"), true);
-        assert_eq!(ProductRanges::is_doubled("222222"), true);
-        assert_eq!(ProductRanges::is_doubled("446446"), true);
-        assert_eq!(ProductRanges::is_doubled("38593859"), true);
+        assert!(is_repeated(11, 2));
+        assert!(is_repeated(22, 2));
+        assert!(!is_repeated(12, 2));
+        assert!(is_repeated(1010, 2));
+        assert!(is_repeated(1188511885, 2));
+        assert!(is_repeated(222222, 2));
+        assert!(is_repeated(446446, 2));
+        assert!(is_repeated(38593859, 2));
     }
 
     #[test]
     fn test_is_repeated() {
-        assert_eq!(ProductRanges::is_repeated("11"), true);
-        assert_eq!(ProductRanges::is_repeated("22"), true);
-        assert_eq!(ProductRanges::is_repeated("99"), true);
-        assert_eq!(ProductRanges::is_repeated("111"), true);
-        assert_eq!(ProductRanges::is_repeated("999"), true);
-        assert_eq!(ProductRanges::is_repeated("1010"), true);
-        assert_eq!(ProductRanges::is_repeated("1188511885"), true);
-        assert_eq!(ProductRanges::is_repeated("222222"), true);
-        assert_eq!(ProductRanges::is_repeated("446446"), true);
-        assert_eq!(ProductRanges::is_repeated("38593859"), true);
-        assert_eq!(ProductRanges::is_repeated("565656"), true);
-        assert_eq!(ProductRanges::is_repeated("824824824"), true);
-        assert_eq!(ProductRanges::is_repeated("2121212121"), true);
+        assert!(is_periodic(11));
+        assert!(is_periodic(22));
+        assert!(is_periodic(99));
+        assert!(is_periodic(111));
+        assert!(is_periodic(999));
+        assert!(is_periodic(1010));
+        assert!(is_periodic(1188511885));
+        assert!(is_periodic(222222));
+        assert!(is_periodic(446446));
+        assert!(is_periodic(38593859));
+        assert!(is_periodic(565656));
+        assert!(is_periodic(824824824));
+        assert!(is_periodic(2121212121));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::digits::{DigitError, largest_number};
 use std::iter::FromIterator;
 
 advent_of_code::solution!(3);
@@ -9,16 +10,18 @@ struct BatteryArray {
 struct BatteryBank(Vec<u8>);
 
 impl BatteryArray {
-    fn from_text(text: &str) -> Self {
-       let banks = text.lines().map(|line| {
-            BatteryBank::from_text(line)
-       }).collect::<Vec<BatteryBank>>();
+    fn from_text(text: &str) -> Option<Self> {
+       let banks = text.lines().enumerate().map(|(index, line)| {
+            BatteryBank::from_text(line).map_err(|error| eprintln!("bank on line {}: {error}", index + 1))
+       }).collect::<Result<Vec<BatteryBank>, _>>().ok()?;
 
-       Self { banks }
+       Some(Self { banks })
     }
 
-    fn total_voltage(&self, max_depth: usize) -> u64 {
-        self.banks.iter().map(|bank| bank.highest_voltage(max_depth).unwrap_or(0)).sum()
+    fn total_voltage(&self, max_depth: usize) -> Option<u64> {
+        self.banks.iter().enumerate().map(|(index, bank)| {
+            bank.highest_voltage(max_depth).map_err(|error| eprintln!("bank on line {}: {error}", index + 1))
+        }).sum::<Result<_, _>>().ok()
     }
 }
 
@@ -29,51 +32,23 @@ impl FromIterator<u8> for BatteryBank {
 }
 
 impl BatteryBank {
-    fn from_text(text: &str) -> Self {
+    fn from_text(text: &str) -> Result<Self, DigitError> {
         text.chars().map(|battery| {
-            battery.to_string().parse::<u8>().unwrap_or(0)
-        }).collect::<BatteryBank>()
+            battery.to_digit(10).map(|digit| digit as u8).ok_or(DigitError::InvalidDigit)
+        }).collect::<Result<BatteryBank, _>>()
     }
 
-    fn highest_voltage(&self, max_depth: usize) -> Option<u64> {
-        BatteryBank::highest_voltage_recursive(max_depth, &mut vec![], &self.0)
+    fn highest_voltage(&self, max_depth: usize) -> Result<u64, DigitError> {
+        largest_number(&self.0, max_depth)
     }
-
-    fn highest_voltage_recursive(max_depth: usize, current_stack: &mut Vec<u8>, candidates: &[u8]) -> Option<u64> {
-        if current_stack.len() >= max_depth {
-            let voltage = current_stack.iter().map(|val| val.to_string()).collect::<Vec<String>>().join("").parse::<u64>().unwrap_or(0);
-            return Some(voltage);
-        }
-
-        if candidates.is_empty() || (candidates.len() + current_stack.len() < max_depth) {
-            return None
-        }
-
-        let remaining_picks = max_depth - current_stack.len();
-        let last_valid_index = candidates.len() - remaining_picks;
-        let valid_candidates = &candidates[0..=last_valid_index];
-
-        let max_value = valid_candidates.iter().max().unwrap();
-        let max_index = valid_candidates.iter().position(|x| x == max_value).unwrap();
-
-        current_stack.push(candidates[max_index]);
-        let result = Self::highest_voltage_recursive(max_depth, current_stack, &candidates[(max_index + 1)..]);
-        current_stack.pop();
-
-        result
-    }
-
 }
-pub fn part_one(input: &str) -> Option<u64> {
-    let total_voltage = BatteryArray::from_text(input).total_voltage(2);
 
-    Some(total_voltage)
+pub fn part_one(input: &str) -> Option<u64> {
+    BatteryArray::from_text(input)?.total_voltage(2)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let total_voltage = BatteryArray::from_text(input).total_voltage(12);
-
-    Some(total_voltage)
+    BatteryArray::from_text(input)?.total_voltage(12)
 }
 
 #[cfg(test)]
@@ -93,19 +68,20 @@ mod tests {
     }
 
     #[test]
-    fn test_highest_voltage_recursive() {
-        let bank = BatteryBank::from_text("987654321111111");
-        let result = BatteryBank::highest_voltage_recursive(2, &mut vec![], &bank.0);
-        assert_eq!(result, Some(98));
-
-        let result = BatteryBank::highest_voltage_recursive(12, &mut vec![], &bank.0);
-        assert_eq!(result, Some(987654321111));
-
-        let bank = BatteryBank::from_text("811111111111119");
-        let result = BatteryBank::highest_voltage_recursive(2, &mut vec![], &bank.0);
-        assert_eq!(result, Some(89));
+    fn test_highest_voltage() {
+        let bank = BatteryBank::from_text("987654321111111").unwrap();
+        assert_eq!(bank.highest_voltage(2), Ok(98));
+        assert_eq!(bank.highest_voltage(12), Ok(987654321111));
+
+        let bank = BatteryBank::from_text("811111111111119").unwrap();
+        assert_eq!(bank.highest_voltage(2), Ok(89));
+        assert_eq!(bank.highest_voltage(12), Ok(811111111119));
+    }
 
-        let result = BatteryBank::highest_voltage_recursive(12, &mut vec![], &bank.0);
-        assert_eq!(result, Some(811111111119))
+    #[test]
+    fn test_invalid_banks() {
+        assert_eq!(BatteryBank::from_text("12a4").err(), Some(DigitError::InvalidDigit));
+        assert_eq!(part_one("12a4\n"), None);
+        assert_eq!(part_two("987654321111111\n12345\n"), None);
     }
 }
@@ -1,103 +1,57 @@
-use itertools::Itertools;
-use std::cmp::max;
+use advent_of_code::interval_set::IntervalSet;
+use advent_of_code::parse::{self, report, ParseError, Span};
 
 advent_of_code::solution!(5);
 
 #[derive(Debug)]
 struct IngredientList {
-    fresh_ingredients: Vec<(u64, u64)>,
+    fresh_ingredients: IntervalSet<u64>,
     available_ingredients: Vec<u64>,
 }
 
-fn consolidate_ranges(range_list: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
-    let mut ranges: Vec<(u64, u64)> = vec![];
-
-    let mut current_range: Option<(u64, u64)> = None;
-
-    for candidate in range_list {
-        match current_range {
-            None => current_range = Some(candidate),
-            Some((start, end)) => {
-                if candidate.0 <= end {
-                    current_range = Some((start, max(end, candidate.1)))
-                } else {
-                    ranges.push(current_range.unwrap());
-                    current_range = Some(candidate);
-                }
-            }
-        }
-    }
-
-    if let Some(range) = current_range {
-        ranges.push(range);
-    }
-
-    ranges
-}
-
 fn check_ingredients(
-    fresh_ingredients: &[(u64, u64)],
+    fresh_ingredients: &IntervalSet<u64>,
     available_ingredients: &[u64],
 ) -> Vec<u64> {
-    let mut matches: Vec<u64> = vec![];
-
-    for ingredient in available_ingredients {
-        for (range_start, range_end) in fresh_ingredients {
-            if ingredient <= range_end && ingredient >= range_start {
-                matches.push(*ingredient);
-                break;
-            }
-        }
-    }
-
-    matches
+    available_ingredients
+        .iter()
+        .copied()
+        .filter(|&ingredient| fresh_ingredients.contains(ingredient))
+        .collect()
 }
 
-fn count_fresh_ingredients(ranges: &[(u64, u64)]) -> u64 {
-    ranges.iter().map(|(start, end)| end - (start - 1)).sum()
+fn count_fresh_ingredients(ranges: &IntervalSet<u64>) -> u64 {
+    ranges.len() as u64
 }
 
 impl IngredientList {
-    fn from_text(input: &str) -> Self {
-        let parts: Vec<&str> = input.split("\n\n").collect();
-
-        let fresh_ingredients = IngredientList::parse_ranges(parts[0]);
-        let available_ingredients = IngredientList::parse_available(parts[1]);
-
-        Self {
-            fresh_ingredients,
-            available_ingredients,
-        }
+    fn from_text(input: &str) -> Result<Self, ParseError> {
+        let input = Span::new(input);
+        let [ranges, available] = input.sections()[..] else {
+            return Err(input.error("expected fresh ranges and available ingredients separated by a blank line"));
+        };
+
+        Ok(Self {
+            fresh_ingredients: IngredientList::parse_ranges(ranges)?,
+            available_ingredients: IngredientList::parse_available(available)?,
+        })
     }
 
-    fn parse_ranges(text: &str) -> Vec<(u64, u64)> {
-        let ranges = text
-            .lines()
-            .flat_map(|line| {
-                line.split(',')
-                    .map(|entry| {
-                        let parts = entry.split('-').collect::<Vec<&str>>();
-                        (
-                            parts[0].parse::<u64>().unwrap_or(0),
-                            parts[1].parse::<u64>().unwrap_or(0),
-                        )
-                    })
-            })
-            .sorted_by(|a, b| Ord::cmp(&a.0, &b.0))
-            .collect::<Vec<(u64, u64)>>();
-
-        consolidate_ranges(ranges)
+    fn parse_ranges(text: Span) -> Result<IntervalSet<u64>, ParseError> {
+        text.split(&[',', '\n'])
+            .into_iter()
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| entry.range())
+            .collect()
     }
 
-    fn parse_available(text: &str) -> Vec<u64> {
-        text.lines()
-            .filter_map(|line| line.parse().ok())
-            .collect()
+    fn parse_available(text: Span) -> Result<Vec<u64>, ParseError> {
+        parse::lines(text, |line| line.parse())
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let list = IngredientList::from_text(input);
+    let list = report(IngredientList::from_text(input))?;
 
     Some(
         check_ingredients(&list.fresh_ingredients, &list.available_ingredients).len() as u64,
@@ -105,7 +59,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let list = IngredientList::from_text(input);
+    let list = report(IngredientList::from_text(input))?;
     Some(count_fresh_ingredients(&list.fresh_ingredients))
 }
 
@@ -126,14 +80,21 @@ mod tests {
     }
 
     #[test]
-    fn test_consolidate_ranges() {
-        let list = vec![(1, 1), (3, 5), (4, 6), (5, 10)];
-        assert_eq!(consolidate_ranges(list), vec![(1, 1), (3, 10)])
+    fn test_parse_ranges() {
+        let ranges = IngredientList::parse_ranges(Span::new("1-1\n5-10\n3-5\n4-6")).unwrap();
+        assert_eq!(ranges.ranges(), &[(1, 1), (3, 10)])
+    }
+
+    #[test]
+    fn test_count_overlapping_ranges() {
+        // ids in more than one range are fresh only once.
+        let ranges = IngredientList::parse_ranges(Span::new("3-5\n10-14\n16-20\n12-18")).unwrap();
+        assert_eq!(count_fresh_ingredients(&ranges), 14);
     }
 
     #[test]
     fn test_check_ingredients() {
-        let fresh_ingredients = vec![(1, 5), (7, 10)];
+        let fresh_ingredients: IntervalSet<u64> = [(1, 5), (7, 10)].into_iter().collect();
         let available_ingredients = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
         assert_eq!(
             check_ingredients(&fresh_ingredients, &available_ingredients),
@@ -1,3 +1,5 @@
+use advent_of_code::text_block::{Direction, Orientation, TextBlock};
+
 advent_of_code::solution!(6);
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -5,146 +7,48 @@ enum Operator {
     Multiply, Add
 }
 
-fn calculate_columns_from_input(text: &str) -> Option<Vec<u64>> {
-    let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).rev().collect();
-
-    if let Some((head, tail)) = lines.split_first() {
-        let operators = parse_operators(head);
-        let totals = process_rows(operators, tail);
-        Some(totals)
-    } else {
-        None
-    }
-}
-
-fn parse_operators(line: &str) -> Vec<Operator> {
-    line.split_whitespace().map(|operator| {
-        match operator {
-            "*" => Operator::Multiply,
-            "+" => Operator::Add,
-            _ => panic!("Unsupported operator")
+impl Operator {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "*" => Some(Operator::Multiply),
+            "+" => Some(Operator::Add),
+            _ => None
         }
-    }).collect()
-}
-
-fn process_rows(operators: Vec<Operator>, rows: &[&str]) -> Vec<u64> {
-    rows.iter().fold(initialize_accumulator(&operators), |mut acc, &line| {
-        for (index, val) in line.split_whitespace().enumerate() {
-            let num = val.parse::<u64>().unwrap_or(0);
-
-            match operators[index] {
-                Operator::Multiply => {
-                    acc[index] *= num
-                },
-                Operator::Add => {
-                    acc[index] += num
-                }
-            }
-        }
-
-        acc
-    })
-}
-
-fn parse_operators_by_index(line: &str) -> Vec<Operator> {
-    let mut current_operator: Option<Operator> = None;
-    line.chars().map(|char| {
-        match char {
-            '*' => current_operator = Some(Operator::Multiply),
-            '+' => current_operator = Some(Operator::Add),
-            _ => {}
-        }
-
-        current_operator.unwrap_or(Operator::Add)
-    }).collect()
-}
-
-fn parse_columnar_input(text: &str) -> Option<Vec<u64>> {
-    let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).rev().collect();
-
-    if let Some((head, tail)) = lines.split_first() {
-        let operators = parse_operators_by_index(head);
-        let reversed: Vec<&str> = tail.iter().cloned().rev().collect();
-        let totals = process_columns(&reversed);
-
-        Some(calculate_columnar_values(operators, totals))
-    } else {
-        None
     }
-}
-
-fn calculate_columnar_values(operators: Vec<Operator>, numbers: Vec<u64>) -> Vec<u64> {
-    let mut totals: Vec<u64> = vec![];
-    let mut sum_index = 0;
-    let mut last_operator = Operator::Add;
 
-    for (index, number) in numbers.into_iter().enumerate() {
-        if number == 0 {
-            sum_index += 1;
-        } else {
-            let operator = operators.get(index).copied().unwrap_or(last_operator);
-            last_operator = operator;
-
-            let new_value = match last_operator {
-                Operator::Add => {
-                    let current = totals.get(sum_index).unwrap_or(&0);
-                    current + number
-                }
-                Operator::Multiply => {
-                    let current = totals.get(sum_index).unwrap_or(&1);
-                    current * number
-                }
-            };
-
-            if sum_index >= totals.len() {
-                totals.resize(sum_index + 1, new_value);
-            } else {
-               totals[sum_index] = new_value
-            }
+    fn apply(&self, numbers: &[u64]) -> u64 {
+        match self {
+            Operator::Multiply => numbers.iter().product(),
+            Operator::Add => numbers.iter().sum()
         }
     }
-
-    totals
 }
 
-fn process_columns(lines: &[&str]) -> Vec<u64> {
-    let mut parsed_values: Vec<Vec<char>> = Vec::new();
+/// Solves every problem of the worksheet, reading the numbers of each problem in the given orientation and direction.
+fn solve_worksheet(text: &str, orientation: Orientation, direction: Direction) -> Option<Vec<u64>> {
+    let block = TextBlock::new(text);
+    let operator_row = block.height().checked_sub(1)?;
 
-    for line in lines.iter() {
-        for (index, val) in line.chars().enumerate() {
-            if val != ' ' {
-                if index >= parsed_values.len() {
-                    parsed_values.resize_with(index + 1, Vec::new);
-                }
-                parsed_values[index].push(val);
-            }
-        }
-    }
+    block.column_groups().iter().map(|group| {
+        let operators = group.with_rows(operator_row..operator_row + 1).read(Orientation::Rows, Direction::LeftToRight);
+        let operator = Operator::from_symbol(operators.first()?)?;
 
-    parsed_values.iter().map(|vals| {
-        if !vals.is_empty() {
-            vals.iter().collect::<String>().parse::<u64>().unwrap_or(0)
-        } else {
-            0
-        }
-    }).collect()
-}
+        let numbers = group.with_rows(0..operator_row)
+            .read(orientation, direction)
+            .iter()
+            .map(|number| number.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
 
-fn initialize_accumulator(operators: &[Operator]) -> Vec<u64> {
-    operators.iter().map(|operator| {
-        match operator {
-            Operator::Multiply => 1,
-            Operator::Add => 0
-        }
+        Some(operator.apply(&numbers))
     }).collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    calculate_columns_from_input(input).map(|result| result.iter().sum())
+    solve_worksheet(input, Orientation::Rows, Direction::LeftToRight).map(|result| result.iter().sum())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    parse_columnar_input(input).map(|result| result.iter().sum())
+    solve_worksheet(input, Orientation::Columns, Direction::RightToLeft).map(|result| result.iter().sum())
 }
 
 #[cfg(test)]
@@ -165,8 +69,15 @@ mod tests {
 
     #[test]
     fn test_parse_operators() {
-        let input = String::from("*   +  *   *  *  +");
-        let result = parse_operators(&input);
+        let input = ["*", "+", "*", "*", "*", "+"];
+        let result: Vec<Operator> = input.iter().filter_map(|symbol| Operator::from_symbol(symbol)).collect();
         assert_eq!(result, vec![Operator::Multiply, Operator::Add, Operator::Multiply, Operator::Multiply, Operator::Multiply, Operator::Add])
     }
+
+    #[test]
+    fn test_zero_columns() {
+        let input = "10 2\n 0 3\n*  +\n";
+        assert_eq!(solve_worksheet(input, Orientation::Rows, Direction::LeftToRight), Some(vec![0, 5]));
+        assert_eq!(solve_worksheet(input, Orientation::Columns, Direction::RightToLeft), Some(vec![0, 23]));
+    }
 }
@@ -1,150 +1,108 @@
-use std::cmp::max;
-use std::collections::{HashMap, HashSet};
-
 advent_of_code::solution!(7);
 
-const BEAM_SPLIT_VECTORS: [(i32, i32); 2] = [(0, -1), (0, 1)];
-
 struct BeamMap {
-    splitter_map: HashMap<usize, Vec<usize>>,
+    cells: Vec<Vec<char>>,
     start_position: (usize, usize),
-    row_size: usize,
     col_size: usize,
 }
 
+/// Result of sending the beam from `S` down through the manifold.
+struct Simulation {
+    /// Number of distinct splitters that were hit by at least one beam.
+    splitters_hit: u64,
+    /// Number of timelines, i.e. paths a single particle could take to the bottom.
+    timelines: u64,
+    /// For every row, how many timelines pass through each column.
+    beams: Vec<Vec<u64>>,
+}
+
 impl BeamMap {
     fn from_text(input: &str) -> Self {
-        let mut splitter_map: HashMap<usize, Vec<usize>> = HashMap::new();
-        let mut start_position = (0, 0);
-        let mut row_size = 0;
-        let mut col_size = 0;
-
-        for (row_index, line) in input.lines().enumerate() {
-            row_size = max(row_size, row_index);
-            for (col_index, char) in line.chars().enumerate() {
-                col_size = max(col_size, col_index + 1);
-                match char {
-                    'S' => start_position = (row_index, col_index),
-                    '^' => splitter_map
-                        .entry(col_index)
-                        .or_default()
-                        .push(row_index),
-                    _ => {}
-                }
-            }
-        }
+        let cells: Vec<Vec<char>> = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let start_position = cells
+            .iter()
+            .enumerate()
+            .find_map(|(row, line)| Some((row, line.iter().position(|&c| c == 'S')?)))
+            .unwrap_or((0, 0));
+        let col_size = cells.iter().map(Vec::len).max().unwrap_or(0);
 
         Self {
-            splitter_map,
+            cells,
             start_position,
-            row_size,
             col_size,
         }
     }
 
-    fn count_visited_splitters(&self) -> Option<u64> {
-        let mut count: u64 = 0;
-        let mut visited: HashSet<(usize, usize)> = HashSet::new();
-        let mut visited_splitters: HashSet<(usize, usize)> = HashSet::new();
-        let mut stack: Vec<(usize, usize)> = vec![];
-
-        if let Some(new_row) =
-            self.find_next_splitter_row(self.start_position.1, self.start_position.0)
-        {
-            let splitter_position = (new_row, self.start_position.1);
-            count += 1;
-            for beam_position in self.get_valid_split_positions(splitter_position) {
-                stack.push(beam_position);
-                visited.insert(beam_position);
-            }
+    fn is_splitter(&self, row: usize, col: usize) -> bool {
+        self.cells[row].get(col) == Some(&'^')
+    }
+
+    /// Sweeps the grid row by row, carrying the number of timelines per column.
+    /// A splitter sends its beams to the neighbouring columns of the same row; beams leaving the grid are dropped.
+    fn simulate(&self) -> Simulation {
+        let mut beams = vec![vec![0; self.col_size]; self.cells.len()];
+        let mut splitters_hit = 0;
+        let mut current = vec![0; self.col_size];
+        if self.col_size > 0 {
+            current[self.start_position.1] = 1;
         }
 
-        while let Some((row, col)) = stack.pop() {
-            if let Some(splitter_row) = self.find_next_splitter_row(col, row) {
-                let split_position = (splitter_row, col);
+        for (row, beam_row) in beams.iter_mut().enumerate().skip(self.start_position.0) {
+            let mut next = vec![0; self.col_size];
 
-                if visited_splitters.contains(&split_position) {
+            for (col, &count) in current.iter().enumerate().filter(|(_, count)| **count > 0) {
+                if !self.is_splitter(row, col) {
+                    next[col] += count;
                     continue;
-                } else {
-                    visited_splitters.insert(split_position);
                 }
 
-                count += 1;
-                for new_position in self.get_valid_split_positions(split_position) {
-                    if !visited.contains(&new_position) {
-                        stack.push(new_position);
-                        visited.insert(new_position);
+                splitters_hit += 1;
+                for new_col in [col.checked_sub(1), Some(col + 1)].into_iter().flatten() {
+                    if new_col < self.col_size {
+                        next[new_col] += count;
                     }
                 }
             }
-        }
-
-        Some(count)
-    }
 
-    fn find_next_splitter_row(&self, col_index: usize, current_row: usize) -> Option<usize> {
-        if let Some(col_splitters) = self.splitter_map.get(&col_index) {
-            col_splitters
-                .iter()
-                .find(|&elem| elem > &current_row).copied()
-        } else {
-            None
+            *beam_row = next.clone();
+            current = next;
         }
-    }
-
-    fn get_valid_split_positions(&self, start_position: (usize, usize)) -> Vec<(usize, usize)> {
-        BEAM_SPLIT_VECTORS
-            .iter()
-            .filter_map(|(row_delta, col_delta)| {
-                let (new_row, new_col) = (
-                    start_position.0.wrapping_add(*row_delta as usize),
-                    start_position.1.wrapping_add(*col_delta as usize),
-                );
-                if new_col <= self.col_size && new_row <= self.row_size {
-                    Some((new_row, new_col))
-                } else {
-                    None
-                }
-            })
-            .collect()
-    }
-
-    fn count_paths(&self) -> u64 {
-        let mut cache: HashMap<(usize, usize), u64> = HashMap::new();
-        self.count_paths_recursive(&mut cache, self.start_position)
-    }
 
-    fn count_paths_recursive(
-        &self,
-        cache: &mut HashMap<(usize, usize), u64>,
-        start: (usize, usize),
-    ) -> u64 {
-        if let Some(cached_count) = cache.get(&start) {
-            return *cached_count;
+        Simulation {
+            splitters_hit,
+            timelines: current.iter().sum(),
+            beams,
         }
+    }
 
-        let mut count = 0;
+    /// Draws the grid with `|` wherever a beam passes through empty space.
+    #[allow(dead_code)] // for debugging: `println!("{}", map.render(&map.simulate()))`
+    fn render(&self, simulation: &Simulation) -> String {
+        let mut output = String::new();
 
-        if let Some(position) = self.find_next_splitter_row(start.1, start.0) {
-            for new_position in self.get_valid_split_positions((position, start.1)) {
-                let sub_count = self.count_paths_recursive(cache, new_position);
-                cache.entry(new_position).or_insert(sub_count);
-                count += sub_count;
+        for (row, line) in self.cells.iter().enumerate() {
+            for (col, &cell) in line.iter().enumerate() {
+                let lit = simulation.beams[row][col] > 0;
+                output.push(if cell == '.' && lit { '|' } else { cell });
             }
-
-            count
-        } else {
-            1
+            output.push('\n');
         }
+
+        output
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    BeamMap::from_text(input).count_visited_splitters()
+    Some(BeamMap::from_text(input).simulate().splitters_hit)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    Some(BeamMap::from_text(input).count_paths())
+    Some(BeamMap::from_text(input).simulate().timelines)
 }
 
 #[cfg(test)]
@@ -162,4 +120,31 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(40));
     }
+
+    #[test]
+    fn test_render() {
+        let map = BeamMap::from_text(&advent_of_code::template::read_file("examples", DAY));
+        let expected = [
+            ".......S.......",
+            ".......|.......",
+            "......|^|......",
+            "......|.|......",
+            ".....|^|^|.....",
+            ".....|.|.|.....",
+            "....|^|^|^|....",
+            "....|.|.|.|....",
+            "...|^|^|||^|...",
+            "...|.|.|||.|...",
+            "..|^|^|||^|^|..",
+            "..|.|.|||.|.|..",
+            ".|^|||^||.||^|.",
+            ".|.|||.||.||.|.",
+            "|^|^|^|^|^|||^|",
+            "|.|.|.|.|.|||.|",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(map.render(&map.simulate()), expected);
+    }
 }
@@ -1,3 +1,5 @@
+use advent_of_code::parse::{self, report, ParseError};
+
 advent_of_code::solution!(8);
 
 fn calculate_distance(a: (isize, isize, isize), b: (isize, isize, isize)) -> isize {
@@ -13,22 +15,11 @@ struct JunctionBoxes {
 }
 
 impl JunctionBoxes {
-    fn from_text(input: &str) -> Self {
-        let boxes = input
-            .lines()
-            .filter_map(|line| {
-                let parts = line
-                    .split(",")
-                    .map(|part| part.parse::<isize>().unwrap_or(0))
-                    .collect::<Vec<isize>>();
-
-                if parts.len() >= 3 {
-                    Some((parts[0], parts[1], parts[2]))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<(isize, isize, isize)>>();
+    fn from_text(input: &str) -> Result<Self, ParseError> {
+        let boxes = parse::lines(input, |line| {
+            let [x, y, z] = line.integer_array::<isize, 3>(&[','])?;
+            Ok((x, y, z))
+        })?;
 
         let mut pairs = vec![];
 
@@ -40,7 +31,7 @@ impl JunctionBoxes {
 
         pairs.sort_by(|a, b| a.0.cmp(&b.0));
 
-        Self { boxes, pairs }
+        Ok(Self { boxes, pairs })
     }
 }
 
@@ -94,7 +85,7 @@ impl UnionFind {
 }
 
 pub fn solve(input: &str, take_count: usize) -> Option<u64> {
-    let junction_boxes = JunctionBoxes::from_text(input);
+    let junction_boxes = report(JunctionBoxes::from_text(input))?;
     let mut uf = UnionFind::new(junction_boxes.boxes.len());
 
     for (_, a, b) in junction_boxes.pairs.iter().take(take_count) {
@@ -113,7 +104,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let junction_boxes = JunctionBoxes::from_text(input);
+    let junction_boxes = report(JunctionBoxes::from_text(input))?;
     let mut uf = UnionFind::new(junction_boxes.boxes.len());
     let mut last_connection: Option<(usize, usize)> = None;
 
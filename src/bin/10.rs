@@ -1,37 +1,33 @@
-use good_lp::{coin_cbc, constraint, variable, variables, Solution, SolverModel};
-use itertools::Itertools;
-use regex::Regex;
+use advent_of_code::gf2::System;
+use advent_of_code::integer_system::{IntegerSystem, SolveError};
+use advent_of_code::parse::{self, report, ParseError, Span};
+use advent_of_code::patterns;
+use advent_of_code::static_regex;
 
 advent_of_code::solution!(10);
 
 #[derive(Debug)]
 struct Machine {
     expected_output: isize,
+    lights: usize,
     button_bitmasks: Vec<isize>,
     button_vectors: Vec<Vec<u8>>,
     joltages: Vec<isize>,
 }
 
 impl Machine {
-    fn from_input(text: &str) -> Option<Self> {
-        let main_re = Regex::new(r"\[([^\]]+)\]\s+(.+?)\s+\{([^\}]+)\}").unwrap();
-        let paren_re = Regex::new(r"\(([^\)]+)\)").unwrap();
-
-        let caps = main_re.captures(text)?;
-
-        let indicator_lights = caps.get(1)?.as_str();
-        let buttons_section = caps.get(2)?.as_str();
-        let joltages_str = caps.get(3)?.as_str();
-
-        let buttons: Vec<&str> = paren_re
-            .captures_iter(buttons_section)
-            .filter_map(|c| c.get(1).map(|m| m.as_str()))
-            .collect();
-
-        let joltages = parse_joltages(joltages_str);
-
-        Some(Self {
-            expected_output: indicator_as_bitmask(indicator_lights),
+    fn from_input<'a>(line: impl Into<Span<'a>>) -> Result<Self, ParseError> {
+        let fields = patterns::captures(static_regex!(r"\[([^\]]+)\]\s+(.+?)\s+\{([^\}]+)\}"), line)?;
+
+        let indicator_lights = fields.span(1)?;
+        let buttons: Vec<Vec<usize>> = patterns::captures_iter(static_regex!(r"\(([^\)]+)\)"), fields.span(2)?)
+            .map(|button| button.span(1)?.integers(&[',']))
+            .collect::<Result<_, _>>()?;
+        let joltages: Vec<isize> = fields.span(3)?.integers(&[','])?;
+
+        Ok(Self {
+            expected_output: indicator_as_bitmask(indicator_lights.text),
+            lights: indicator_lights.text.len(),
             button_bitmasks: buttons.iter().map(|b| button_as_bitmask(b)).collect(),
             button_vectors: buttons.iter().map(|b| button_as_vector(b, joltages.len())).collect(),
             joltages,
@@ -39,45 +35,40 @@ impl Machine {
     }
 
     fn find_solution_for_lights(&self) -> Option<usize> {
-        (0..=self.button_bitmasks.len())
-            .find_map(|count| {
-                self.button_bitmasks
-                    .iter()
-                    .combinations(count)
-                    .find(|combo| combo.iter().copied().fold(0, |acc, x| acc ^ x) == self.expected_output)
-                    .map(|_| count)
-            })
-    }
+        let mut system = System::new(self.button_bitmasks.len());
 
-    fn find_solution_for_joltages(&self) -> Option<usize> {
-        let num_buttons = self.button_vectors.len();
-        let num_counters = self.joltages.len();
+        for light in 0..self.lights {
+            let toggling: Vec<usize> = self
+                .button_bitmasks
+                .iter()
+                .enumerate()
+                .filter(|(_, mask)| *mask & (1 << light) != 0)
+                .map(|(button, _)| button)
+                .collect();
+
+            system.add_equation(&toggling, self.expected_output & (1 << light) != 0);
+        }
 
-        let mut vars = variables!();
-        let button_presses: Vec<_> = (0..num_buttons)
-            .map(|_| vars.add(variable().integer().min(0)))
-            .collect();
+        system.solve()?.min_weight().map(|presses| presses.count_ones())
+    }
 
-        let objective: good_lp::Expression = button_presses.iter().sum();
-        let mut problem = vars.minimise(objective).using(coin_cbc);
+    fn find_solution_for_joltages(&self) -> Result<usize, SolveError> {
+        let mut system = IntegerSystem::new(self.button_vectors.len());
 
-        for counter_idx in 0..num_counters {
-            let expr: good_lp::Expression = self.button_vectors
+        for (counter_idx, &joltage) in self.joltages.iter().enumerate() {
+            let terms: Vec<(usize, i64)> = self
+                .button_vectors
                 .iter()
                 .enumerate()
                 .filter(|(_, button)| button[counter_idx] == 1)
-                .map(|(button_idx, _)| button_presses[button_idx])
-                .sum();
+                .map(|(button, _)| (button, 1))
+                .collect();
 
-            problem = problem.with(constraint!(expr == self.joltages[counter_idx] as i32));
+            system.add_equation(&terms, joltage as i64);
         }
 
-        problem.solve().ok().map(|solution| {
-            button_presses
-                .iter()
-                .map(|&v| solution.value(v).round() as usize)
-                .sum()
-        })
+        let presses = system.minimise_sum()?;
+        Ok(presses.iter().sum::<u64>() as usize)
     }
 }
 
@@ -91,16 +82,13 @@ fn indicator_as_bitmask(input: &str) -> isize {
     isize::from_str_radix(&binary_string, 2).unwrap_or(0)
 }
 
-fn button_as_bitmask(input: &str) -> isize {
-    input
-        .split(',')
-        .filter_map(|s| s.trim().parse::<usize>().ok())
-        .fold(0, |acc, pos| acc | (1 << pos))
+fn button_as_bitmask(positions: &[usize]) -> isize {
+    positions.iter().fold(0, |acc, pos| acc | (1 << pos))
 }
 
-fn button_as_vector(input: &str, num_counters: usize) -> Vec<u8> {
+fn button_as_vector(positions: &[usize], num_counters: usize) -> Vec<u8> {
     let mut result = vec![0u8; num_counters];
-    for pos in input.split(',').filter_map(|s| s.trim().parse::<usize>().ok()) {
+    for &pos in positions {
         if pos < num_counters {
             result[pos] = 1;
         }
@@ -108,27 +96,26 @@ fn button_as_vector(input: &str, num_counters: usize) -> Vec<u8> {
     result
 }
 
-fn parse_joltages(input: &str) -> Vec<isize> {
-    input
-        .split(',')
-        .filter_map(|s| s.trim().parse().ok())
-        .collect()
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
-    let sum: usize = input
-        .lines()
-        .filter_map(|line| Machine::from_input(line)?.find_solution_for_lights())
-        .sum();
+    let sum: usize = report(parse::lines(input, Machine::from_input))?
+        .iter()
+        .map(Machine::find_solution_for_lights)
+        .sum::<Option<_>>()?;
 
     Some(sum as u64)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let sum: usize = input
-        .lines()
-        .filter_map(|line| Machine::from_input(line)?.find_solution_for_joltages())
-        .sum();
+    let sum: usize = report(parse::lines(input, Machine::from_input))?
+        .iter()
+        .enumerate()
+        .map(|(index, machine)| {
+            machine
+                .find_solution_for_joltages()
+                .map_err(|error| eprintln!("machine on line {}: {error}", index + 1))
+        })
+        .sum::<Result<_, _>>()
+        .ok()?;
 
     Some(sum as u64)
 }
@@ -151,8 +138,17 @@ mod tests {
 
     #[test]
     fn test_parse_buttons() {
-        assert_eq!(button_as_bitmask("1,3"), 0b1010);
-        assert_eq!(button_as_bitmask("3,5,4,7"), 0b10111000);
+        assert_eq!(button_as_bitmask(&[1, 3]), 0b1010);
+        assert_eq!(button_as_bitmask(&[3, 5, 4, 7]), 0b10111000);
+    }
+
+    #[test]
+    fn test_invalid_machine() {
+        let error = Machine::from_input("[.##.] (3) (1,3)\n").err().unwrap();
+        assert!(error.message.starts_with("expected text matching"));
+
+        let error = parse::lines("[.#] (0) {1,1}\n[.#] (0,x) {1,1}\n", Machine::from_input).err().unwrap();
+        assert_eq!((error.line, error.column), (2, 9));
     }
 
     #[test]
@@ -175,20 +171,31 @@ mod tests {
         let example3 = Machine::from_input("[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}")
             .unwrap().find_solution_for_lights();
         assert_eq!(example3, Some(2));
+
+        // the diagram has more lights than there are joltages.
+        let more_lights = Machine::from_input("[..#] (2) (0,1) {1,1}").unwrap().find_solution_for_lights();
+        assert_eq!(more_lights, Some(1));
     }
 
     #[test]
     fn test_find_solution_for_joltages() {
         let example1 = Machine::from_input("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}")
             .unwrap().find_solution_for_joltages();
-        assert_eq!(example1, Some(10));
+        assert_eq!(example1, Ok(10));
 
         let example2 = Machine::from_input("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}")
             .unwrap().find_solution_for_joltages();
-        assert_eq!(example2, Some(12));
+        assert_eq!(example2, Ok(12));
 
         let example3 = Machine::from_input("[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}")
             .unwrap().find_solution_for_joltages();
-        assert_eq!(example3, Some(11));
+        assert_eq!(example3, Ok(11));
+
+        // button (2) affects no counter.
+        let unused_button = Machine::from_input("[.#] (1) (0,1) (2) {1,2}")
+            .unwrap().find_solution_for_joltages();
+        assert_eq!(unused_button, Ok(2));
+
+        assert_eq!(part_two("[.#] (1) {1,1}\n"), None);
     }
 }
\ No newline at end of file
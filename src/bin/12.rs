@@ -1,8 +1,10 @@
 use advent_of_code::dlx::Arena;
-use good_lp::{constraint, default_solver, variable, Expression, Solution, SolverModel, Variable};
+use advent_of_code::ilp::{Comparison, Model, Outcome, Row};
 use itertools::Itertools;
+use advent_of_code::parse::{self, report, ParseError, Span};
+use advent_of_code::patterns;
+use advent_of_code::static_regex;
 use rayon::prelude::*;
-use regex::Regex;
 use std::cmp::max;
 use std::collections::HashMap;
 
@@ -191,8 +193,8 @@ impl Puzzle {
         positions
     }
 
-    /// Solve using Integer Linear Programming
-    fn find_solution_via_ilp(&self, shapes: &HashMap<usize, Shape>) -> Option<bool> {
+    /// Solve using Integer Linear Programming. Returns an error for unknown shapes and solver failures, e.g. without a backend.
+    fn find_solution_via_ilp(&self, shapes: &HashMap<usize, Shape>) -> Result<bool, String> {
         // Early check: pieces must fit in grid (can't need more cells than available)
         let total_cells_needed: usize = self
             .requirements
@@ -208,7 +210,7 @@ impl Puzzle {
         let grid_size = self.width * self.height;
         if total_cells_needed > grid_size {
             // Can't fit all pieces - too many cells needed
-            return Some(false);
+            return Ok(false);
         }
 
         // Generate all possible placements
@@ -217,7 +219,9 @@ impl Puzzle {
         let mut placements: Vec<(usize, Vec<usize>)> = vec![];
 
         for (piece_instance, &shape_idx) in piece_indices.iter().enumerate() {
-            let shape = shapes.get(&shape_idx)?;
+            let shape = shapes
+                .get(&shape_idx)
+                .ok_or_else(|| format!("puzzle requires unknown shape {shape_idx}"))?;
 
             for permutation in &shape.permutations {
                 for (start_row, start_col) in self.valid_positions_for_permutation(permutation) {
@@ -236,51 +240,40 @@ impl Puzzle {
         }
 
         if placements.is_empty() {
-            return Some(false);
+            return Ok(false);
         }
 
-        // Create ILP problem
-        use good_lp::ProblemVariables;
-        let mut problem = ProblemVariables::new();
-
-        // Create binary variable for each placement
-        let vars: Vec<Variable> = placements
-            .iter()
-            .map(|_| problem.add(variable().binary()))
-            .collect();
-
-        // Objective: just find feasibility (minimize 0)
-        let objective: Expression = vars.iter().map(|&v| v * 0.0).sum();
-        let mut model = problem.minimise(objective).using(default_solver);
+        // Create ILP problem with a binary variable for each placement
+        let mut model = Model::new();
+        let vars: Vec<_> = placements.iter().map(|_| model.binary()).collect();
 
         // Constraint: each piece instance placed exactly once
         for piece_instance in 0..piece_indices.len() {
-            let piece_vars: Expression = placements
+            let piece_vars = placements
                 .iter()
                 .enumerate()
                 .filter(|(_, (pi, _))| *pi == piece_instance)
-                .map(|(i, _)| vars[i])
-                .sum();
+                .map(|(i, _)| vars[i]);
 
-            model = model.with(constraint!(piece_vars == 1));
+            model.add_row(Row::sum_of(piece_vars, Comparison::Eq, 1.0));
         }
 
         // Constraint: each cell covered at most once
         for cell in 0..grid_size {
-            let cell_vars: Expression = placements
+            let cell_vars = placements
                 .iter()
                 .enumerate()
                 .filter(|(_, (_, cells))| cells.contains(&cell))
-                .map(|(i, _)| vars[i])
-                .sum();
+                .map(|(i, _)| vars[i]);
 
-            model = model.with(constraint!(cell_vars <= 1));
+            model.add_row(Row::sum_of(cell_vars, Comparison::Le, 1.0));
         }
 
-        // Solve
+        // No objective: any feasible assignment will do
         match model.solve() {
-            Ok(_solution) => Some(true),
-            Err(_) => Some(false),
+            Outcome::Optimal(_) | Outcome::Feasible(_) => Ok(true),
+            Outcome::Infeasible => Ok(false),
+            Outcome::Error(message) => Err(message),
         }
     }
 }
@@ -292,28 +285,23 @@ struct PuzzleInput {
 }
 
 impl PuzzleInput {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
         let mut shapes = HashMap::new();
         let mut puzzles = vec![];
+        let shape_re = static_regex!(r"^(\d+):\r?\n([\s\S]+)$");
 
-        for group in input.split("\n\n") {
-            let re = Regex::new(r"^(\d+):\n([\s\S]+)$").unwrap();
-
-            if let Some(caps) = re.captures(group) {
-                let index: usize = caps[1].parse().unwrap();
-                let shape_str = &caps[2];
+        for group in Span::new(input).sections() {
+            if shape_re.is_match(group.text) {
+                let fields = patterns::captures(shape_re, group)?;
+                let index: usize = fields.get(1)?;
 
-                shapes.entry(index).or_insert(Shape::from_input(shape_str));
+                shapes.entry(index).or_insert(Shape::from_input(fields.span(2)?.text));
             } else {
-                for line in group.lines() {
-                    if let Some(puzzle) = Self::parse_puzzle(line) {
-                        puzzles.push(puzzle);
-                    }
-                }
+                puzzles.extend(parse::lines(group, Self::parse_puzzle)?);
             }
         }
 
-        Self { shapes, puzzles }
+        Ok(Self { shapes, puzzles })
     }
 
     fn parse_shape(shape_input: &str) -> Vec<Point> {
@@ -331,37 +319,28 @@ impl PuzzleInput {
         points
     }
 
-    fn parse_puzzle(puzzle_input: &str) -> Option<Puzzle> {
-        let re = Regex::new(r"^(\d+)x(\d+): (.+)$").unwrap();
-
-        if let Some(caps) = re.captures(puzzle_input) {
-            let width: usize = caps[1].parse().unwrap();
-            let height: usize = caps[2].parse().unwrap();
-            let mut requirements: HashMap<usize, usize> = HashMap::new();
+    fn parse_puzzle<'a>(puzzle_input: impl Into<Span<'a>>) -> Result<Puzzle, ParseError> {
+        let fields = patterns::captures(static_regex!(r"^(\d+)x(\d+): (.+)$"), puzzle_input)?;
+        let width: usize = fields.get(1)?;
+        let height: usize = fields.get(2)?;
+        let mut requirements: HashMap<usize, usize> = HashMap::new();
 
-            for (i, count) in caps[3]
-                .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .enumerate()
-            {
-                requirements.entry(i).or_insert(count);
-            }
-
-            Some(Puzzle {
-                width,
-                height,
-                requirements,
-            })
-        } else {
-            None
+        for (i, count) in fields.span(3)?.integers(&[' ', '\t'])?.into_iter().enumerate() {
+            requirements.entry(i).or_insert(count);
         }
+
+        Ok(Puzzle {
+            width,
+            height,
+            requirements,
+        })
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
     use std::sync::atomic::{AtomicUsize, Ordering};
 
-    let puzzle_input = PuzzleInput::from_input(input);
+    let puzzle_input = report(PuzzleInput::from_input(input))?;
     let total = puzzle_input.puzzles.len();
 
     let solved = AtomicUsize::new(0);
@@ -370,7 +349,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     puzzle_input
         .puzzles
         .par_iter()
-        .for_each(|puzzle| {
+        .try_for_each(|puzzle| {
             // Early check: cells needed must fit in grid
             let cells_needed: usize = puzzle
                 .requirements
@@ -386,7 +365,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
             if cells_needed <= puzzle.width * puzzle.height {
                 // Use ILP to solve
-                if puzzle.find_solution_via_ilp(&puzzle_input.shapes) == Some(true) {
+                if puzzle.find_solution_via_ilp(&puzzle_input.shapes)? {
                     solved.fetch_add(1, Ordering::Relaxed);
                 }
             }
@@ -400,7 +379,11 @@ pub fn part_one(input: &str) -> Option<u64> {
                     solved.load(Ordering::Relaxed)
                 );
             }
-        });
+
+            Ok::<_, String>(())
+        })
+        .map_err(|error| eprintln!("failed to solve puzzle: {error}"))
+        .ok()?;
 
     Some(solved.load(Ordering::Relaxed) as u64)
 }
@@ -528,7 +511,7 @@ mod tests {
     #[test]
     fn test_shape_ordering() {
         let input = "0:\n#\n\n1:\n##\n\n2:\n###";
-        let puzzle_input = PuzzleInput::from_input(input);
+        let puzzle_input = PuzzleInput::from_input(input).unwrap();
 
         let shapes: Vec<Shape> = puzzle_input.shapes.values().cloned().collect();
 
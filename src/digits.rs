@@ -0,0 +1,144 @@
+use std::fmt::Display;
+
+/// Which subsequence [`select_digits`] should keep.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Selection {
+    Largest,
+    Smallest,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DigitError {
+    NotEnoughDigits,
+    InvalidDigit,
+    Overflow,
+}
+
+impl Display for DigitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DigitError::NotEnoughDigits => write!(f, "fewer digits available than requested."),
+            DigitError::InvalidDigit => write!(f, "encountered a value that is not a decimal digit."),
+            DigitError::Overflow => write!(f, "selected digits do not fit the target integer."),
+        }
+    }
+}
+
+/// Picks the lexicographically largest (or smallest) subsequence of `k` digits in `O(n)`.
+///
+/// A digit is popped from the stack whenever a better one follows and enough digits remain to still fill `k` slots.
+pub fn select_digits(digits: &[u8], k: usize, selection: Selection) -> Result<Vec<u8>, DigitError> {
+    if k > digits.len() {
+        return Err(DigitError::NotEnoughDigits);
+    }
+
+    let mut drops = digits.len() - k;
+    let mut stack: Vec<u8> = Vec::with_capacity(digits.len());
+
+    for &digit in digits {
+        if digit > 9 {
+            return Err(DigitError::InvalidDigit);
+        }
+
+        while drops > 0 {
+            let improves = match (stack.last(), selection) {
+                (Some(&top), Selection::Largest) => top < digit,
+                (Some(&top), Selection::Smallest) => top > digit,
+                (None, _) => false,
+            };
+
+            if !improves {
+                break;
+            }
+
+            stack.pop();
+            drops -= 1;
+        }
+
+        stack.push(digit);
+    }
+
+    stack.truncate(k);
+    Ok(stack)
+}
+
+pub fn to_u64(digits: &[u8]) -> Result<u64, DigitError> {
+    digits.iter().try_fold(0u64, |acc, &digit| {
+        acc.checked_mul(10)
+            .and_then(|acc| acc.checked_add(u64::from(digit)))
+            .ok_or(DigitError::Overflow)
+    })
+}
+
+pub fn to_u128(digits: &[u8]) -> Result<u128, DigitError> {
+    digits.iter().try_fold(0u128, |acc, &digit| {
+        acc.checked_mul(10)
+            .and_then(|acc| acc.checked_add(u128::from(digit)))
+            .ok_or(DigitError::Overflow)
+    })
+}
+
+/// The largest number formed by keeping `k` of the digits in order.
+pub fn largest_number(digits: &[u8], k: usize) -> Result<u64, DigitError> {
+    to_u64(&select_digits(digits, k, Selection::Largest)?)
+}
+
+/// The smallest number formed by keeping `k` of the digits in order, leading zeroes allowed.
+pub fn smallest_number(digits: &[u8], k: usize) -> Result<u64, DigitError> {
+    to_u64(&select_digits(digits, k, Selection::Smallest)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(text: &str) -> Vec<u8> {
+        text.bytes().map(|b| b - b'0').collect()
+    }
+
+    #[test]
+    fn test_largest_number() {
+        assert_eq!(largest_number(&digits("987654321111111"), 2), Ok(98));
+        assert_eq!(largest_number(&digits("987654321111111"), 12), Ok(987654321111));
+        assert_eq!(largest_number(&digits("811111111111119"), 2), Ok(89));
+        assert_eq!(largest_number(&digits("234234234234278"), 12), Ok(434234234278));
+        assert_eq!(largest_number(&digits("818181911112111"), 12), Ok(888911112111));
+        assert_eq!(largest_number(&digits("12"), 0), Ok(0));
+    }
+
+    #[test]
+    fn test_smallest_number() {
+        assert_eq!(smallest_number(&digits("1432219"), 4), Ok(1219));
+        assert_eq!(smallest_number(&digits("10200"), 4), Ok(200));
+        assert_eq!(smallest_number(&digits("9"), 1), Ok(9));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let input = digits("3141592653589");
+
+        for k in 0..=6 {
+            let expected = (0..1u32 << input.len())
+                .filter(|mask| mask.count_ones() as usize == k)
+                .map(|mask| {
+                    (0..input.len())
+                        .filter(|i| mask & (1 << i) != 0)
+                        .map(|i| input[i])
+                        .collect::<Vec<u8>>()
+                })
+                .max()
+                .unwrap();
+
+            assert_eq!(select_digits(&input, k, Selection::Largest), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(largest_number(&digits("123"), 4), Err(DigitError::NotEnoughDigits));
+        assert_eq!(largest_number(&[1, 12], 1), Err(DigitError::InvalidDigit));
+        assert_eq!(largest_number(&[9; 25], 20), Err(DigitError::Overflow));
+        assert_eq!(to_u128(&[9; 25]), Ok(10u128.pow(25) - 1));
+        assert_eq!(to_u128(&[9; 40]), Err(DigitError::Overflow));
+    }
+}
@@ -0,0 +1,782 @@
+//! Geometry helpers for orthogonal (rectilinear) polygons on an integer grid.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Point(pub i64, pub i64);
+
+/// An inclusive, axis-aligned bounding box.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub max_x: i64,
+    pub min_y: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    pub fn from_points(a: Point, b: Point) -> Self {
+        Self {
+            min_x: a.0.min(b.0),
+            max_x: a.0.max(b.0),
+            min_y: a.1.min(b.1),
+            max_y: a.1.max(b.1),
+        }
+    }
+
+    /// Number of grid cells covered, counting both borders.
+    pub fn area(&self) -> i64 {
+        (self.max_x - self.min_x + 1) * (self.max_y - self.min_y + 1)
+    }
+
+    pub fn x_strictly_contains(&self, x: i64) -> bool {
+        self.min_x < x && x < self.max_x
+    }
+
+    pub fn y_strictly_contains(&self, y: i64) -> bool {
+        self.min_y < y && y < self.max_y
+    }
+
+    pub fn y_overlaps(&self, other: &Bounds) -> bool {
+        self.min_y < other.max_y && self.max_y > other.min_y
+    }
+
+    pub fn x_overlaps(&self, other: &Bounds) -> bool {
+        self.min_x < other.max_x && self.max_x > other.min_x
+    }
+
+    /// Checks whether the closed boxes share at least one point.
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        self.min_x <= other.max_x
+            && other.min_x <= self.max_x
+            && self.min_y <= other.max_y
+            && other.min_y <= self.max_y
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.min_x <= p.0 && p.0 <= self.max_x && self.min_y <= p.1 && p.1 <= self.max_y
+    }
+}
+
+/// A horizontal or vertical segment between two grid points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub a: Point,
+    pub b: Point,
+}
+
+impl Edge {
+    pub fn from_points(a: Point, b: Point) -> Self {
+        Self { a, b }
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.a.0 == self.b.0
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.a.1 == self.b.1
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::from_points(self.a, self.b)
+    }
+
+    pub fn length(&self) -> i64 {
+        (self.a.0 - self.b.0).abs() + (self.a.1 - self.b.1).abs()
+    }
+
+    /// Checks whether the segments share at least one point, including endpoints and collinear overlap.
+    /// An axis-aligned segment is its own bounding box, so this is a box intersection test.
+    pub fn intersects(&self, other: &Edge) -> bool {
+        self.bounds().intersects(&other.bounds())
+    }
+
+    /// Checks whether two perpendicular segments cross at a point interior to both.
+    pub fn crosses(&self, other: &Edge) -> bool {
+        let (vertical, horizontal) = match (self.is_vertical(), other.is_vertical()) {
+            (true, false) => (self, other),
+            (false, true) => (other, self),
+            _ => return false,
+        };
+
+        let vb = vertical.bounds();
+        let hb = horizontal.bounds();
+        hb.x_strictly_contains(vb.min_x) && vb.y_strictly_contains(hb.min_y)
+    }
+
+    fn contains_doubled(&self, x2: i64, y2: i64) -> bool {
+        let b = self.bounds();
+        2 * b.min_x <= x2 && x2 <= 2 * b.max_x && 2 * b.min_y <= y2 && y2 <= 2 * b.max_y
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rectangle {
+    pub bounds: Bounds,
+}
+
+impl Rectangle {
+    pub fn from_points(a: Point, b: Point) -> Self {
+        Self {
+            bounds: Bounds::from_points(a, b),
+        }
+    }
+
+    pub fn area(&self) -> i64 {
+        self.bounds.area()
+    }
+
+    /// Checks whether `edge` passes through the open interior of the rectangle.
+    /// Edges running along the rectangle's border do not slice it.
+    pub fn is_sliced_by(&self, edge: &Edge) -> bool {
+        let rb = &self.bounds;
+        let eb = edge.bounds();
+
+        if edge.is_vertical() {
+            rb.x_strictly_contains(eb.min_x) && rb.y_overlaps(&eb)
+        } else {
+            rb.y_strictly_contains(eb.min_y) && rb.x_overlaps(&eb)
+        }
+    }
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A closed orthogonal polygon given by its vertices in order; the last vertex connects back to the first.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+    edges: Vec<Edge>,
+}
+
+impl Polygon {
+    pub fn from_vertices(vertices: Vec<Point>) -> Self {
+        let edges = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| Edge::from_points(a, b))
+            .collect();
+
+        Self { vertices, edges }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Geometric area via the shoelace formula, treating vertices as points.
+    pub fn area(&self) -> i64 {
+        let twice_area: i64 = self
+            .edges
+            .iter()
+            .map(|edge| edge.a.0 * edge.b.1 - edge.b.0 * edge.a.1)
+            .sum();
+
+        twice_area.abs() / 2
+    }
+
+    pub fn perimeter(&self) -> i64 {
+        self.edges.iter().map(Edge::length).sum()
+    }
+
+    /// Number of grid cells on or inside the boundary, by Pick's theorem.
+    pub fn tile_count(&self) -> i64 {
+        self.area() + self.perimeter() / 2 + 1
+    }
+
+    pub fn locate(&self, p: Point) -> Location {
+        self.locate_doubled(2 * p.0, 2 * p.1)
+    }
+
+    pub fn contains_point(&self, p: Point) -> bool {
+        self.locate(p) != Location::Outside
+    }
+
+    /// Locates a point given in half-unit coordinates, so that midpoints between grid lines can be tested exactly.
+    fn locate_doubled(&self, x2: i64, y2: i64) -> Location {
+        if self.edges.iter().any(|edge| edge.contains_doubled(x2, y2)) {
+            return Location::Boundary;
+        }
+
+        // cast a ray towards +x and count the vertical edges it crosses.
+        // the half-open y-interval ensures a ray through a vertex is counted once.
+        let crossings = self
+            .edges
+            .iter()
+            .filter(|edge| {
+                let eb = edge.bounds();
+                edge.is_vertical() && 2 * eb.min_x > x2 && 2 * eb.min_y <= y2 && y2 < 2 * eb.max_y
+            })
+            .count();
+
+        if crossings % 2 == 1 {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Checks whether the closed rectangle lies within the closed polygon, borders included.
+    pub fn contains_rectangle(&self, rect: &Rectangle) -> bool {
+        let b = rect.bounds;
+
+        if b.min_x < b.max_x && b.min_y < b.max_y {
+            // without an edge through the interior, the interior is entirely in- or outside.
+            // its midpoint is tested in half-units, so it can never land on a grid line by rounding.
+            !self.edges.iter().any(|edge| rect.is_sliced_by(edge))
+                && self.locate_doubled(b.min_x + b.max_x, b.min_y + b.max_y) == Location::Inside
+        } else {
+            self.contains_segment(b)
+        }
+    }
+
+    /// Checks a degenerate rectangle (a segment or a single point) by testing every breakpoint
+    /// where an edge meets it and the midpoints in between.
+    fn contains_segment(&self, b: Bounds) -> bool {
+        let horizontal = b.min_y == b.max_y;
+        let (from, to) = if horizontal {
+            (b.min_x, b.max_x)
+        } else {
+            (b.min_y, b.max_y)
+        };
+
+        let mut breakpoints: Vec<i64> = self
+            .vertices
+            .iter()
+            .map(|p| if horizontal { p.0 } else { p.1 })
+            .filter(|&v| from < v && v < to)
+            .chain([from, to])
+            .collect();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        let samples = breakpoints
+            .iter()
+            .map(|v| 2 * v)
+            .chain(breakpoints.windows(2).map(|w| w[0] + w[1]));
+
+        samples.into_iter().all(|v2| {
+            let location = if horizontal {
+                self.locate_doubled(v2, 2 * b.min_y)
+            } else {
+                self.locate_doubled(2 * b.min_x, v2)
+            };
+            location != Location::Outside
+        })
+    }
+
+    /// Finds the largest rectangle (by tile count) spanned by two vertices that lies within the polygon.
+    pub fn largest_inscribed_rectangle(&self) -> Option<InscribedRectangle> {
+        let grid = ContainmentGrid::new(self);
+        let mut best: Option<InscribedRectangle> = None;
+
+        for (i, &a) in self.vertices.iter().enumerate() {
+            for (j, &b) in self.vertices.iter().enumerate().skip(i) {
+                let rectangle = Rectangle::from_points(a, b);
+
+                if best.is_some_and(|best| best.rectangle.area() >= rectangle.area()) {
+                    continue;
+                }
+
+                if grid.contains_spanned(i, j) {
+                    best = Some(InscribedRectangle {
+                        corners: [a, b],
+                        rectangle,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InscribedRectangle {
+    /// The polygon vertices the rectangle is spanned by.
+    pub corners: [Point; 2],
+    pub rectangle: Rectangle,
+}
+
+/// Answers rectangle-in-polygon queries after `O(n²)` preprocessing: in `O(1)` for rectangles spanned by two
+/// vertices, and in `O(log n)` for arbitrary rectangles, whose coordinates have to be looked up first.
+///
+/// The plane is compressed to the distinct vertex coordinates plus one slot for each gap between them.
+/// Every point within a slot behaves the same with respect to the polygon, so classifying one representative
+/// per slot and keeping a 2D prefix sum of outside slots reduces a query to four lookups.
+#[derive(Debug, Clone)]
+pub struct ContainmentGrid {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// `outside[sx][sy]` counts the outside slots in `0..sx` × `0..sy`.
+    outside: Vec<Vec<u32>>,
+    /// Slots of each vertex, in the order of [`Polygon::vertices`].
+    vertex_slots: Vec<(usize, usize)>,
+}
+
+impl ContainmentGrid {
+    pub fn new(polygon: &Polygon) -> Self {
+        let mut xs: Vec<i64> = polygon.vertices.iter().map(|p| p.0).collect();
+        let mut ys: Vec<i64> = polygon.vertices.iter().map(|p| p.1).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+
+        let width = (2 * xs.len()).saturating_sub(1);
+        let height = (2 * ys.len()).saturating_sub(1);
+        let mut inside = vec![vec![false; height]; width];
+
+        for edge in &polygon.edges {
+            let b = edge.bounds();
+            let (Some(x_from), Some(x_to), Some(y_from), Some(y_to)) = (
+                Self::slot(&xs, b.min_x),
+                Self::slot(&xs, b.max_x),
+                Self::slot(&ys, b.min_y),
+                Self::slot(&ys, b.max_y),
+            ) else {
+                continue;
+            };
+
+            for column in &mut inside[x_from..=x_to] {
+                column[y_from..=y_to].fill(true);
+            }
+        }
+
+        for sy in 0..height {
+            let y2 = Self::doubled(&ys, sy);
+
+            let mut crossings: Vec<usize> = polygon
+                .edges
+                .iter()
+                .filter(|edge| {
+                    let eb = edge.bounds();
+                    edge.is_vertical() && 2 * eb.min_y <= y2 && y2 < 2 * eb.max_y
+                })
+                .filter_map(|edge| Self::slot(&xs, edge.a.0))
+                .collect();
+            crossings.sort_unstable();
+
+            let mut passed = 0;
+            for (sx, column) in inside.iter_mut().enumerate() {
+                while passed < crossings.len() && crossings[passed] < sx {
+                    passed += 1;
+                }
+                column[sy] |= passed % 2 == 1;
+            }
+        }
+
+        let mut outside = vec![vec![0; height + 1]; width + 1];
+        for sx in 0..width {
+            for sy in 0..height {
+                outside[sx + 1][sy + 1] = outside[sx][sy + 1] + outside[sx + 1][sy]
+                    - outside[sx][sy]
+                    + u32::from(!inside[sx][sy]);
+            }
+        }
+
+        let vertex_slots = polygon
+            .vertices
+            .iter()
+            .map(|p| {
+                (
+                    2 * xs.partition_point(|&x| x < p.0),
+                    2 * ys.partition_point(|&y| y < p.1),
+                )
+            })
+            .collect();
+
+        Self {
+            xs,
+            ys,
+            outside,
+            vertex_slots,
+        }
+    }
+
+    /// Maps a coordinate to its slot: even slots are vertex coordinates, odd slots the gaps between them.
+    fn slot(coords: &[i64], value: i64) -> Option<usize> {
+        match coords.binary_search(&value) {
+            Ok(index) => Some(2 * index),
+            Err(index) if index == 0 || index == coords.len() => None,
+            Err(index) => Some(2 * index - 1),
+        }
+    }
+
+    /// Representative coordinate of a slot, in half-units.
+    fn doubled(coords: &[i64], slot: usize) -> i64 {
+        if slot.is_multiple_of(2) {
+            2 * coords[slot / 2]
+        } else {
+            coords[slot / 2] + coords[slot / 2 + 1]
+        }
+    }
+
+    /// Checks whether the closed rectangle lies within the closed polygon, borders included.
+    pub fn contains_rectangle(&self, rect: &Rectangle) -> bool {
+        let b = rect.bounds;
+        let (Some(x_from), Some(x_to), Some(y_from), Some(y_to)) = (
+            Self::slot(&self.xs, b.min_x),
+            Self::slot(&self.xs, b.max_x),
+            Self::slot(&self.ys, b.min_y),
+            Self::slot(&self.ys, b.max_y),
+        ) else {
+            return false;
+        };
+
+        self.contains_slots(x_from, x_to, y_from, y_to)
+    }
+
+    /// Checks whether the rectangle spanned by the vertices at indices `a` and `b` lies within the polygon.
+    pub fn contains_spanned(&self, a: usize, b: usize) -> bool {
+        let ((ax, ay), (bx, by)) = (self.vertex_slots[a], self.vertex_slots[b]);
+        self.contains_slots(ax.min(bx), ax.max(bx), ay.min(by), ay.max(by))
+    }
+
+    fn contains_slots(&self, x_from: usize, x_to: usize, y_from: usize, y_to: usize) -> bool {
+        let o = &self.outside;
+        o[x_to + 1][y_to + 1] + o[x_from][y_from] - o[x_from][y_to + 1] - o[x_to + 1][y_from] == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::Rng;
+
+    fn square() -> Polygon {
+        //   (0,10) ------- (10,10)
+        //     |              |
+        //     |              |
+        //   (0,0) -------- (10,0)
+        Polygon::from_vertices(vec![Point(0, 0), Point(10, 0), Point(10, 10), Point(0, 10)])
+    }
+
+    #[test]
+    fn test_bounds_strictly_contains() {
+        let bounds = Bounds::from_points(Point(2, 2), Point(8, 6));
+
+        assert!(bounds.x_strictly_contains(5));
+        assert!(!bounds.x_strictly_contains(1));
+        assert!(!bounds.x_strictly_contains(9));
+        assert!(!bounds.x_strictly_contains(2)); // on boundary
+        assert!(!bounds.x_strictly_contains(8)); // on boundary
+
+        assert!(bounds.y_strictly_contains(4));
+        assert!(!bounds.y_strictly_contains(1));
+        assert!(!bounds.y_strictly_contains(7));
+        assert!(!bounds.y_strictly_contains(2)); // on boundary
+        assert!(!bounds.y_strictly_contains(6)); // on boundary
+    }
+
+    #[test]
+    fn test_bounds_overlaps() {
+        let bounds = Bounds::from_points(Point(2, 2), Point(8, 6));
+
+        // Overlapping y-ranges
+        let overlapping = Bounds::from_points(Point(0, 4), Point(0, 9));
+        assert!(bounds.y_overlaps(&overlapping));
+
+        // Non-overlapping y-ranges (above)
+        let above = Bounds::from_points(Point(0, 7), Point(0, 9));
+        assert!(!bounds.y_overlaps(&above));
+
+        // Non-overlapping y-ranges (below)
+        let below = Bounds::from_points(Point(0, 0), Point(0, 1));
+        assert!(!bounds.y_overlaps(&below));
+    }
+
+    #[test]
+    fn test_rectangle_is_sliced_by() {
+        let rect = Rectangle::from_points(Point(2, 2), Point(8, 6));
+
+        // === VERTICAL EDGES ===
+
+        // Vertical edge slices through middle (x=5, from y=0 to y=9)
+        assert!(rect.is_sliced_by(&Edge::from_points(Point(5, 0), Point(5, 9))));
+
+        // Vertical edges left and right of the rectangle
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(1, 0), Point(1, 9))));
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(9, 0), Point(9, 9))));
+
+        // Vertical edges ON the boundary touch but don't slice
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(2, 0), Point(2, 9))));
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(8, 0), Point(8, 9))));
+
+        // Vertical edge in x-range but y-range doesn't overlap (x=5, y=7 to y=9)
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(5, 7), Point(5, 9))));
+
+        // Vertical edge in x-range, y-range partially overlaps
+        assert!(rect.is_sliced_by(&Edge::from_points(Point(5, 4), Point(5, 9))));
+
+        // === HORIZONTAL EDGES ===
+
+        // Horizontal edge slices through middle (y=4, from x=0 to x=9)
+        assert!(rect.is_sliced_by(&Edge::from_points(Point(0, 4), Point(9, 4))));
+
+        // Horizontal edges above and below the rectangle
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(0, 1), Point(9, 1))));
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(0, 7), Point(9, 7))));
+
+        // Horizontal edge ON the boundary (y=2)
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(0, 2), Point(9, 2))));
+
+        // Horizontal edge in y-range but x-range doesn't overlap (y=4, x=9 to x=10)
+        assert!(!rect.is_sliced_by(&Edge::from_points(Point(9, 4), Point(10, 4))));
+    }
+
+    #[test]
+    fn test_edge_intersections() {
+        let vertical = Edge::from_points(Point(5, 0), Point(5, 10));
+
+        assert!(vertical.intersects(&Edge::from_points(Point(0, 5), Point(10, 5))));
+        assert!(vertical.crosses(&Edge::from_points(Point(0, 5), Point(10, 5))));
+
+        // touching at an endpoint intersects but does not cross
+        assert!(vertical.intersects(&Edge::from_points(Point(5, 10), Point(8, 10))));
+        assert!(!vertical.crosses(&Edge::from_points(Point(5, 10), Point(8, 10))));
+        assert!(!vertical.crosses(&Edge::from_points(Point(0, 5), Point(5, 5))));
+
+        // collinear overlap
+        assert!(vertical.intersects(&Edge::from_points(Point(5, 8), Point(5, 12))));
+        assert!(!vertical.crosses(&Edge::from_points(Point(5, 8), Point(5, 12))));
+
+        assert!(!vertical.intersects(&Edge::from_points(Point(6, 0), Point(9, 0))));
+    }
+
+    #[test]
+    fn test_locate() {
+        let polygon = square();
+
+        assert_eq!(polygon.locate(Point(5, 5)), Location::Inside);
+        assert_eq!(polygon.locate(Point(0, 0)), Location::Boundary);
+        assert_eq!(polygon.locate(Point(10, 5)), Location::Boundary);
+        assert_eq!(polygon.locate(Point(5, 10)), Location::Boundary);
+        assert_eq!(polygon.locate(Point(15, 5)), Location::Outside);
+        assert_eq!(polygon.locate(Point(5, -1)), Location::Outside);
+        assert_eq!(polygon.locate(Point(5, 11)), Location::Outside);
+        assert_eq!(polygon.locate(Point(-5, 10)), Location::Outside);
+    }
+
+    #[test]
+    fn test_area() {
+        let polygon = square();
+        assert_eq!(polygon.area(), 100);
+        assert_eq!(polygon.perimeter(), 40);
+        assert_eq!(polygon.tile_count(), 121);
+    }
+
+    #[test]
+    fn test_contains_rectangle() {
+        let polygon = square();
+
+        // Rectangle fully inside: (2,2) to (5,5)
+        assert!(polygon.contains_rectangle(&Rectangle::from_points(Point(2, 2), Point(5, 5))));
+
+        // Rectangle fully outside: (12,12) to (15,15)
+        assert!(!polygon.contains_rectangle(&Rectangle::from_points(Point(12, 12), Point(15, 15))));
+
+        // Rectangle with corners on boundary but inside: (0,0) to (5,5)
+        assert!(polygon.contains_rectangle(&Rectangle::from_points(Point(0, 0), Point(5, 5))));
+
+        // Rectangle that extends outside: (5,5) to (15,15)
+        assert!(!polygon.contains_rectangle(&Rectangle::from_points(Point(5, 5), Point(15, 15))));
+
+        // Degenerate rectangles along the border and through the interior
+        assert!(polygon.contains_rectangle(&Rectangle::from_points(Point(0, 0), Point(10, 0))));
+        assert!(polygon.contains_rectangle(&Rectangle::from_points(Point(3, 0), Point(3, 10))));
+        assert!(!polygon.contains_rectangle(&Rectangle::from_points(Point(3, 5), Point(12, 5))));
+    }
+
+    #[test]
+    fn test_contains_rectangle_centered_on_edge() {
+        // A U-shape whose notch spans x=3..4 down to y=2:
+        //
+        //   (0,6)-(3,6)   (4,6)-(7,6)
+        //     |      |     |      |
+        //     |    (3,2)-(4,2)    |
+        //   (0,0) ------------- (7,0)
+        let polygon = Polygon::from_vertices(vec![
+            Point(0, 0),
+            Point(7, 0),
+            Point(7, 6),
+            Point(4, 6),
+            Point(4, 2),
+            Point(3, 2),
+            Point(3, 6),
+            Point(0, 6),
+        ]);
+
+        // Truncating the center of (3,2)-(4,6) lands on the edge x=3, but the rectangle covers the notch.
+        assert!(!polygon.contains_rectangle(&Rectangle::from_points(Point(3, 2), Point(4, 6))));
+        assert!(polygon.contains_rectangle(&Rectangle::from_points(Point(0, 0), Point(7, 2))));
+        assert!(polygon.contains_rectangle(&Rectangle::from_points(Point(4, 2), Point(7, 6))));
+    }
+
+    /// A random skyline: columns of random width and height standing on `y = 0`.
+    /// Returns the polygon and the column boundaries with their heights.
+    fn random_skyline(rng: &mut Rng) -> (Polygon, Vec<(i64, i64, i64)>) {
+        let mut columns = vec![];
+        let mut x = rng.next(3);
+
+        for _ in 0..1 + rng.next(5) {
+            let width = 1 + rng.next(4);
+            let height = 1 + rng.next(6);
+            columns.push((x, x + width, height));
+            x += width;
+        }
+
+        let mut vertices = vec![Point(columns[0].0, 0)];
+        for &(from, to, height) in &columns {
+            vertices.push(Point(from, height));
+            vertices.push(Point(to, height));
+        }
+        vertices.push(Point(x, 0));
+
+        (Polygon::from_vertices(vertices), columns)
+    }
+
+    /// Reference height of the skyline at a half-unit x coordinate.
+    fn skyline_height(columns: &[(i64, i64, i64)], x2: i64) -> Option<i64> {
+        columns
+            .iter()
+            .filter(|(from, to, _)| 2 * from <= x2 && x2 <= 2 * to)
+            .map(|(_, _, height)| *height)
+            .max()
+    }
+
+    #[test]
+    fn test_property_locate_matches_skyline() {
+        let mut rng = Rng(0x2025_0009);
+
+        for _ in 0..200 {
+            let (polygon, columns) = random_skyline(&mut rng);
+
+            for x in -1..25 {
+                for y in -1..8 {
+                    let inside = skyline_height(&columns, 2 * x).is_some_and(|h| 0 <= y && y <= h);
+                    assert_eq!(
+                        polygon.contains_point(Point(x, y)),
+                        inside,
+                        "{polygon:?} @ {x},{y}"
+                    );
+                }
+            }
+
+            let expected_area: i64 = columns.iter().map(|(from, to, h)| (to - from) * h).sum();
+            assert_eq!(polygon.area(), expected_area);
+        }
+    }
+
+    #[test]
+    fn test_largest_inscribed_rectangle() {
+        let polygon = Polygon::from_vertices(vec![
+            Point(7, 1),
+            Point(11, 1),
+            Point(11, 7),
+            Point(9, 7),
+            Point(9, 5),
+            Point(2, 5),
+            Point(2, 3),
+            Point(7, 3),
+        ]);
+
+        let largest = polygon.largest_inscribed_rectangle().unwrap();
+        assert_eq!(largest.rectangle.area(), 24);
+        assert_eq!(largest.corners, [Point(9, 5), Point(2, 3)]);
+    }
+
+    #[test]
+    fn test_containment_grid() {
+        let polygon = square();
+        let grid = ContainmentGrid::new(&polygon);
+
+        assert!(grid.contains_rectangle(&Rectangle::from_points(Point(2, 2), Point(5, 5))));
+        assert!(grid.contains_rectangle(&Rectangle::from_points(Point(0, 0), Point(10, 10))));
+        assert!(!grid.contains_rectangle(&Rectangle::from_points(Point(5, 5), Point(15, 15))));
+        assert!(!grid.contains_rectangle(&Rectangle::from_points(Point(-1, 0), Point(5, 5))));
+    }
+
+    #[test]
+    fn test_property_containment_grid_matches_polygon() {
+        let mut rng = Rng(0x2025_0929);
+
+        for _ in 0..200 {
+            let (polygon, _) = random_skyline(&mut rng);
+            let grid = ContainmentGrid::new(&polygon);
+
+            for _ in 0..50 {
+                let a = Point(rng.next(24) - 1, rng.next(8) - 1);
+                let b = Point(rng.next(24) - 1, rng.next(8) - 1);
+                let rect = Rectangle::from_points(a, b);
+
+                assert_eq!(
+                    grid.contains_rectangle(&rect),
+                    polygon.contains_rectangle(&rect),
+                    "{polygon:?} {rect:?}"
+                );
+            }
+
+            let vertices = polygon.vertices();
+            for (i, &a) in vertices.iter().enumerate() {
+                for (j, &b) in vertices.iter().enumerate() {
+                    let rect = Rectangle::from_points(a, b);
+                    assert_eq!(grid.contains_spanned(i, j), grid.contains_rectangle(&rect));
+                }
+            }
+
+            let expected = polygon
+                .vertices()
+                .iter()
+                .flat_map(|&a| {
+                    polygon
+                        .vertices()
+                        .iter()
+                        .map(move |&b| Rectangle::from_points(a, b))
+                })
+                .filter(|rect| polygon.contains_rectangle(rect))
+                .map(|rect| rect.area())
+                .max();
+
+            let largest = polygon
+                .largest_inscribed_rectangle()
+                .map(|r| r.rectangle.area());
+            assert_eq!(largest, expected);
+        }
+    }
+
+    #[test]
+    fn test_property_contains_rectangle_matches_sampling() {
+        let mut rng = Rng(0x2025_1209);
+
+        for _ in 0..200 {
+            let (polygon, columns) = random_skyline(&mut rng);
+
+            for _ in 0..50 {
+                let a = Point(rng.next(24) - 1, rng.next(8) - 1);
+                let b = Point(rng.next(24) - 1, rng.next(8) - 1);
+                let rect = Rectangle::from_points(a, b);
+                let rb = rect.bounds;
+
+                let expected = (2 * rb.min_x..=2 * rb.max_x).all(|x2| {
+                    skyline_height(&columns, x2).is_some_and(|h| rb.min_y >= 0 && rb.max_y <= h)
+                });
+
+                assert_eq!(
+                    polygon.contains_rectangle(&rect),
+                    expected,
+                    "{polygon:?} {rect:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Linear algebra over GF(2), the field of bits where addition is XOR.
+
+/// A fixed-length vector of bits packed into 64-bit words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn from_indices(len: usize, indices: &[usize]) -> Self {
+        let mut vector = Self::zeros(len);
+        for &index in indices {
+            vector.set(index, true);
+        }
+        vector
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "bit index {index} out of range");
+        if value {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    pub fn xor_assign(&mut self, other: &BitVector) {
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word ^= other_word;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&index| self.get(index))
+    }
+}
+
+/// A system of linear equations `A x = b` over GF(2).
+#[derive(Debug, Clone)]
+pub struct System {
+    variables: usize,
+    rows: Vec<(BitVector, bool)>,
+}
+
+/// All solutions of a [`System`]: `particular` XOR any combination of the `null_space` basis vectors.
+#[derive(Debug, Clone)]
+pub struct SolutionSpace {
+    pub particular: BitVector,
+    pub null_space: Vec<BitVector>,
+}
+
+impl System {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            rows: vec![],
+        }
+    }
+
+    /// Adds the equation `x[v1] ^ x[v2] ^ ... = rhs`.
+    pub fn add_equation(&mut self, variables: &[usize], rhs: bool) {
+        self.rows
+            .push((BitVector::from_indices(self.variables, variables), rhs));
+    }
+
+    /// Solves the system by Gauss-Jordan elimination, returning `None` if it is inconsistent.
+    pub fn solve(&self) -> Option<SolutionSpace> {
+        let mut rows = self.rows.clone();
+        let mut pivots: Vec<usize> = vec![];
+
+        for column in 0..self.variables {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|&r| rows[r].0.get(column)) else {
+                continue;
+            };
+            rows.swap(rank, found);
+
+            let (pivot_row, pivot_rhs) = rows[rank].clone();
+            for (index, (row, rhs)) in rows.iter_mut().enumerate() {
+                if index != rank && row.get(column) {
+                    row.xor_assign(&pivot_row);
+                    *rhs ^= pivot_rhs;
+                }
+            }
+
+            pivots.push(column);
+        }
+
+        if rows[pivots.len()..].iter().any(|(_, rhs)| *rhs) {
+            return None;
+        }
+
+        let mut particular = BitVector::zeros(self.variables);
+        for (rank, &column) in pivots.iter().enumerate() {
+            particular.set(column, rows[rank].1);
+        }
+
+        let null_space = (0..self.variables)
+            .filter(|column| !pivots.contains(column))
+            .map(|free| {
+                let mut basis = BitVector::zeros(self.variables);
+                basis.set(free, true);
+                for (rank, &column) in pivots.iter().enumerate() {
+                    if rows[rank].0.get(free) {
+                        basis.set(column, true);
+                    }
+                }
+                basis
+            })
+            .collect();
+
+        Some(SolutionSpace {
+            particular,
+            null_space,
+        })
+    }
+}
+
+impl SolutionSpace {
+    /// Number of distinct solutions, saturating at `u128::MAX`.
+    pub fn count(&self) -> u128 {
+        1u128.checked_shl(self.null_space.len() as u32).unwrap_or(u128::MAX)
+    }
+
+    /// Finds a solution with the fewest set bits by walking all `2^k` combinations of the null space in Gray code order,
+    /// so that consecutive candidates differ by a single XOR.
+    /// Returns `None` if the null space has 64 or more dimensions, which is too many to enumerate.
+    pub fn min_weight(&self) -> Option<BitVector> {
+        if self.null_space.len() >= 64 {
+            return None;
+        }
+
+        let mut current = self.particular.clone();
+        let mut best = current.clone();
+        let mut best_weight = best.count_ones();
+
+        for step in 1u64..(1 << self.null_space.len()) {
+            current.xor_assign(&self.null_space[step.trailing_zeros() as usize]);
+
+            let weight = current.count_ones();
+            if weight < best_weight {
+                best_weight = weight;
+                best = current.clone();
+            }
+        }
+
+        Some(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_vector() {
+        let mut vector = BitVector::from_indices(130, &[0, 64, 129]);
+        assert_eq!(vector.count_ones(), 3);
+        assert!(vector.get(64));
+        assert!(!vector.get(65));
+
+        vector.xor_assign(&BitVector::from_indices(130, &[0, 1]));
+        assert_eq!(vector.iter_ones().collect::<Vec<_>>(), vec![1, 64, 129]);
+    }
+
+    #[test]
+    fn test_solve_unique() {
+        // x0 ^ x1 = 1, x1 = 1  =>  x0 = 0, x1 = 1
+        let mut system = System::new(2);
+        system.add_equation(&[0, 1], true);
+        system.add_equation(&[1], true);
+
+        let solution = system.solve().unwrap();
+        assert_eq!(solution.particular.iter_ones().collect::<Vec<_>>(), vec![1]);
+        assert!(solution.null_space.is_empty());
+        assert_eq!(solution.count(), 1);
+    }
+
+    #[test]
+    fn test_solve_inconsistent() {
+        let mut system = System::new(2);
+        system.add_equation(&[0, 1], true);
+        system.add_equation(&[0, 1], false);
+
+        assert!(system.solve().is_none());
+    }
+
+    #[test]
+    fn test_solution_space_is_complete() {
+        // lights `.##.` toggled by buttons (3) (1,3) (2) (2,3) (0,2) (0,1)
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let target = [false, true, true, false];
+
+        let mut system = System::new(buttons.len());
+        for (light, &on) in target.iter().enumerate() {
+            let toggling: Vec<usize> = (0..buttons.len())
+                .filter(|&b| buttons[b].contains(&light))
+                .collect();
+            system.add_equation(&toggling, on);
+        }
+
+        let solution = system.solve().unwrap();
+        let presses_all = |mask: u32| {
+            let mut lights = [false; 4];
+            for (b, button) in buttons.iter().enumerate() {
+                if mask & (1 << b) != 0 {
+                    for &light in *button {
+                        lights[light] ^= true;
+                    }
+                }
+            }
+            lights == target
+        };
+
+        let brute_force_count = (0..1u32 << buttons.len()).filter(|&m| presses_all(m)).count();
+        assert_eq!(solution.count(), brute_force_count as u128);
+
+        let best = solution.min_weight().unwrap();
+        assert_eq!(best.count_ones(), 2);
+        let mask = best.iter_ones().fold(0, |acc, b| acc | (1 << b));
+        assert!(presses_all(mask));
+    }
+
+    #[test]
+    fn test_min_weight_of_large_null_space() {
+        let solution = System::new(64).solve().unwrap();
+        assert_eq!(solution.null_space.len(), 64);
+        assert_eq!(solution.min_weight(), None);
+
+        let solution = System::new(63).solve().unwrap();
+        assert_eq!(solution.count(), 1 << 63);
+    }
+}
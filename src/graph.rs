@@ -0,0 +1,724 @@
+//! Directed graph helpers.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// Number types that [`count_paths`] can accumulate into.
+pub trait PathCount: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// The sum of both counts, or `None` if it does not fit.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+impl PathCount for u64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+}
+
+impl PathCount for u128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u128::checked_add(*self, *other)
+    }
+}
+
+/// A count modulo `M`, for puzzles that ask for the answer "modulo some prime".
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Modular<const M: u64>(pub u64);
+
+impl<const M: u64> PathCount for Modular<M> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn one() -> Self {
+        Self(1 % M)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self(((u128::from(self.0) + u128::from(other.0)) % u128::from(M)) as u64))
+    }
+}
+
+/// An arbitrarily large count, stored as little-endian base 10^9 limbs so it can be printed cheaply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigCount(Vec<u32>);
+
+const LIMB: u32 = 1_000_000_000;
+
+impl From<u64> for BigCount {
+    fn from(mut value: u64) -> Self {
+        let mut limbs = vec![];
+        while value > 0 {
+            limbs.push((value % u64::from(LIMB)) as u32);
+            value /= u64::from(LIMB);
+        }
+        Self(limbs)
+    }
+}
+
+impl PathCount for BigCount {
+    fn zero() -> Self {
+        Self(vec![])
+    }
+
+    fn one() -> Self {
+        Self(vec![1])
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let mut limbs = Vec::with_capacity(self.0.len().max(other.0.len()) + 1);
+        let mut carry = 0;
+
+        for i in 0..self.0.len().max(other.0.len()) {
+            let sum = self.0.get(i).unwrap_or(&0) + other.0.get(i).unwrap_or(&0) + carry;
+            limbs.push(sum % LIMB);
+            carry = sum / LIMB;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+
+        Some(Self(limbs))
+    }
+}
+
+impl Display for BigCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, rest)) => {
+                write!(f, "{most_significant}")?;
+                rest.iter().rev().try_for_each(|limb| write!(f, "{limb:09}"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError<K> {
+    /// The nodes of a cycle that lies on a path from the start to the end, in order.
+    Cycle(Vec<K>),
+    /// More waypoints than fit into the 64-bit visited mask.
+    TooManyWaypoints(usize),
+    Overflow,
+}
+
+impl<K: Display> Display for PathError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::Cycle(nodes) => {
+                write!(f, "graph contains a cycle: ")?;
+                for node in nodes {
+                    write!(f, "{node} -> ")?;
+                }
+                match nodes.first() {
+                    Some(first) => write!(f, "{first}."),
+                    None => write!(f, "(empty)."),
+                }
+            }
+            PathError::TooManyWaypoints(count) => write!(f, "{count} waypoints exceed the supported maximum of 64."),
+            PathError::Overflow => write!(f, "path count does not fit the chosen number type."),
+        }
+    }
+}
+
+/// A directed graph whose node labels are interned to dense ids `0..len()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    labels: Vec<String>,
+    ids: HashMap<String, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+/// The strongly connected components of a [`Graph`], collapsed into a DAG.
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    /// Node ids of each component, components in topological order.
+    pub components: Vec<Vec<usize>>,
+    /// Component index of every node.
+    pub component_of: Vec<usize>,
+    /// One node per component, labelled with its members joined by `,`.
+    pub graph: Graph,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses lines of the form `name: a b c` into edges from `name` to each listed node.
+    /// Lines without a colon are skipped.
+    pub fn parse(input: &str) -> Self {
+        let mut graph = Self::new();
+
+        for (node, outputs) in input.lines().filter_map(|line| line.split_once(':')) {
+            let from = graph.intern(node.trim());
+            for output in outputs.split_whitespace() {
+                let to = graph.intern(output);
+                graph.add_edge(from, to);
+            }
+        }
+
+        graph
+    }
+
+    /// The id of `label`, adding it as a new node if it is unknown.
+    pub fn intern(&mut self, label: &str) -> usize {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+
+        let id = self.labels.len();
+        self.labels.push(label.to_owned());
+        self.ids.insert(label.to_owned(), id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, label: &str) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> &str {
+        &self.labels[id]
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.edges[from].push(to);
+    }
+
+    pub fn successors(&self, id: usize) -> &[usize] {
+        &self.edges[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// The same nodes with every edge pointing the other way.
+    pub fn reverse(&self) -> Self {
+        let mut edges = vec![vec![]; self.len()];
+        for (from, successors) in self.edges.iter().enumerate() {
+            for &to in successors {
+                edges[to].push(from);
+            }
+        }
+
+        Self {
+            labels: self.labels.clone(),
+            ids: self.ids.clone(),
+            edges,
+        }
+    }
+
+    /// Which nodes can be reached from `start`, including `start` itself.
+    pub fn reachable_from(&self, start: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        seen[start] = true;
+
+        while let Some(node) = stack.pop() {
+            for &next in &self.edges[node] {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Orders the nodes so that every edge points forward (Kahn's algorithm).
+    /// If the graph has a cycle, its nodes are returned as the error, starting from the smallest id.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+        for &to in self.edges.iter().flatten() {
+            in_degree[to] += 1;
+        }
+
+        let mut order: Vec<usize> = (0..self.len()).filter(|&n| in_degree[n] == 0).collect();
+        let mut next = 0;
+        while let Some(&node) = order.get(next) {
+            next += 1;
+            for &to in &self.edges[node] {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    order.push(to);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            return Ok(order);
+        }
+
+        // every node left over has a predecessor that is left over too, so walking backwards must close a cycle
+        let predecessors = self.reverse();
+        let mut node = (0..self.len()).find(|&n| in_degree[n] > 0).unwrap();
+        let mut position = vec![None; self.len()];
+        let mut walk = vec![];
+        while position[node].is_none() {
+            position[node] = Some(walk.len());
+            walk.push(node);
+            node = *predecessors.edges[node].iter().find(|&&p| in_degree[p] > 0).unwrap();
+        }
+
+        let mut cycle = walk.split_off(position[node].unwrap());
+        cycle.reverse();
+        // start at the smallest id so the reported cycle does not depend on where the walk began
+        let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+        cycle.rotate_left(smallest);
+        Err(cycle)
+    }
+
+    /// Strongly connected components in topological order, using an iterative version of Tarjan's algorithm.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let mut index = vec![UNVISITED; self.len()];
+        let mut low_link = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut counter = 0;
+
+        for root in 0..self.len() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+
+            let mut call_stack = vec![(root, 0)];
+            while let Some(&mut (node, ref mut next)) = call_stack.last_mut() {
+                if *next == 0 {
+                    index[node] = counter;
+                    low_link[node] = counter;
+                    counter += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&successor) = self.edges[node].get(*next) {
+                    *next += 1;
+                    if index[successor] == UNVISITED {
+                        call_stack.push((successor, 0));
+                    } else if on_stack[successor] {
+                        low_link[node] = low_link[node].min(index[successor]);
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+
+                if low_link[node] == index[node] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        // Tarjan finishes components in reverse topological order
+        components.reverse();
+        components
+    }
+
+    /// Collapses every strongly connected component into a single node.
+    pub fn condensation(&self) -> Condensation {
+        let components = self.strongly_connected_components();
+
+        let mut component_of = vec![0; self.len()];
+        for (component, members) in components.iter().enumerate() {
+            for &member in members {
+                component_of[member] = component;
+            }
+        }
+
+        let mut graph = Self::new();
+        for members in &components {
+            let label = members.iter().map(|&m| self.label(m)).collect::<Vec<_>>().join(",");
+            graph.intern(&label);
+        }
+        for (from, successors) in self.edges.iter().enumerate() {
+            for &to in successors {
+                let (a, b) = (component_of[from], component_of[to]);
+                if a != b && !graph.edges[a].contains(&b) {
+                    graph.add_edge(a, b);
+                }
+            }
+        }
+
+        Condensation {
+            components,
+            component_of,
+            graph,
+        }
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for label in &self.labels {
+            dot.push_str(&format!("    {label:?};\n"));
+        }
+        for (from, successors) in self.edges.iter().enumerate() {
+            for &to in successors {
+                dot.push_str(&format!("    {:?} -> {:?};\n", self.labels[from], self.labels[to]));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Counts the paths from `from` to `to` through all `waypoints`, see [`count_paths`].
+    pub fn count_paths<C: PathCount>(&self, from: usize, to: usize, waypoints: &[usize]) -> Result<C, PathError<usize>> {
+        count_paths_by_id(&self.edges, from, to, waypoints)
+    }
+}
+
+/// Counts the paths from `from` to `to` that pass through every node in `waypoints`.
+///
+/// Nodes without an entry in `adjacency` have no outgoing edges. Every path ends the first time it reaches `to`.
+/// A cycle that is reachable from `from` and can reach `to` would allow infinitely many paths, so it is reported as
+/// [`PathError::Cycle`]. Cycles that cannot reach `to` are ignored.
+/// The search is iterative, so long chains do not overflow the stack.
+pub fn count_paths<'a, K, Q, C>(
+    adjacency: &'a HashMap<K, Vec<K>>,
+    from: &'a Q,
+    to: &'a Q,
+    waypoints: &[&'a Q],
+) -> Result<C, PathError<K>>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    C: PathCount,
+{
+    let mut ids: HashMap<&Q, usize> = HashMap::new();
+    let mut nodes: Vec<&Q> = vec![];
+    let mut intern = |node: &'a Q| -> usize {
+        *ids.entry(node).or_insert_with(|| {
+            nodes.push(node);
+            nodes.len() - 1
+        })
+    };
+
+    let from_id = intern(from);
+    let to_id = intern(to);
+    let waypoint_ids: Vec<usize> = waypoints.iter().map(|&waypoint| intern(waypoint)).collect();
+
+    let mut edges: Vec<(usize, Vec<usize>)> = vec![];
+    for (node, successors) in adjacency {
+        let successor_ids = successors.iter().map(|s| intern(s.borrow())).collect();
+        edges.push((intern(node.borrow()), successor_ids));
+    }
+
+    let mut dense = vec![vec![]; nodes.len()];
+    for (node, successors) in edges {
+        dense[node] = successors;
+    }
+
+    count_paths_by_id(&dense, from_id, to_id, &waypoint_ids).map_err(|error| match error {
+        PathError::Cycle(cycle) => PathError::Cycle(cycle.into_iter().map(|id| nodes[id].to_owned()).collect()),
+        PathError::TooManyWaypoints(count) => PathError::TooManyWaypoints(count),
+        PathError::Overflow => PathError::Overflow,
+    })
+}
+
+fn count_paths_by_id<C: PathCount>(
+    edges: &[Vec<usize>],
+    from: usize,
+    to: usize,
+    waypoints: &[usize],
+) -> Result<C, PathError<usize>> {
+    if waypoints.len() > 64 {
+        return Err(PathError::TooManyWaypoints(waypoints.len()));
+    }
+
+    let mut bits = vec![0u64; edges.len()];
+    for (index, &waypoint) in waypoints.iter().enumerate() {
+        bits[waypoint] |= 1 << index;
+    }
+    let required = if waypoints.len() == 64 { u64::MAX } else { (1 << waypoints.len()) - 1 };
+
+    // only nodes that can reach `to` are searched, so cycles elsewhere in the graph do not matter
+    let mut predecessors = vec![vec![]; edges.len()];
+    for (node, successors) in edges.iter().enumerate().filter(|&(node, _)| node != to) {
+        for &successor in successors {
+            predecessors[successor].push(node);
+        }
+    }
+    let mut reaches_to = vec![false; edges.len()];
+    reaches_to[to] = true;
+    let mut queue = vec![to];
+    while let Some(node) = queue.pop() {
+        for &predecessor in &predecessors[node] {
+            if !reaches_to[predecessor] {
+                reaches_to[predecessor] = true;
+                queue.push(predecessor);
+            }
+        }
+    }
+
+    // for every finished node, the number of paths to `to` keyed by the set of waypoints they visit
+    let mut ways: Vec<Option<HashMap<u64, C>>> = vec![None; edges.len()];
+    // position in `stack` of the nodes on the current DFS path
+    let mut position: Vec<Option<usize>> = vec![None; edges.len()];
+    let mut stack = vec![(from, 0)];
+    position[from] = Some(0);
+
+    while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+        let successors: &[usize] = if node == to { &[] } else { &edges[node] };
+
+        if let Some(&successor) = successors.get(*next) {
+            *next += 1;
+
+            if !reaches_to[successor] {
+                continue;
+            }
+            if let Some(start) = position[successor] {
+                return Err(PathError::Cycle(stack[start..].iter().map(|&(n, _)| n).collect()));
+            }
+            if ways[successor].is_none() {
+                position[successor] = Some(stack.len());
+                stack.push((successor, 0));
+            }
+            continue;
+        }
+
+        stack.pop();
+        position[node] = None;
+
+        let mut counts: HashMap<u64, C> = HashMap::new();
+        if node == to {
+            counts.insert(bits[node], C::one());
+        }
+        for &successor in successors {
+            for (&mask, count) in ways[successor].iter().flatten() {
+                let entry = counts.entry(mask | bits[node]).or_insert_with(C::zero);
+                *entry = entry.checked_add(count).ok_or(PathError::Overflow)?;
+            }
+        }
+        ways[node] = Some(counts);
+    }
+
+    Ok(ways[from]
+        .as_ref()
+        .and_then(|counts| counts.get(&required))
+        .cloned()
+        .unwrap_or_else(C::zero))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjacency(input: &str) -> HashMap<String, Vec<String>> {
+        input
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(node, outputs)| {
+                let outputs = outputs.split_whitespace().map(str::to_owned).collect();
+                (node.to_owned(), outputs)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let graph = adjacency("a: b c\nb: d\nc: d e\nd: e\n");
+
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "e", &[]), Ok(3));
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "e", &["d"]), Ok(2));
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "e", &["b", "c"]), Ok(0));
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "a", &["a"]), Ok(1));
+        assert_eq!(count_paths::<_, _, u64>(&graph, "e", "a", &[]), Ok(0));
+    }
+
+    #[test]
+    fn test_count_paths_stops_at_target() {
+        let graph = adjacency("a: b\nb: c\nc: d\n");
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "b", &[]), Ok(1));
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "b", &["c"]), Ok(0));
+    }
+
+    #[test]
+    fn test_cycle_is_reported() {
+        let graph = adjacency("a: b\nb: c\nc: b d\n");
+        let result = count_paths::<_, _, u64>(&graph, "a", "d", &[]);
+        assert_eq!(result, Err(PathError::Cycle(vec!["b".to_owned(), "c".to_owned()])));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "graph contains a cycle: b -> c -> b."
+        );
+    }
+
+    #[test]
+    fn test_cycle_that_cannot_reach_end_is_ignored() {
+        // b <-> c hangs off the graph and never leads to d
+        let graph = adjacency("a: b d
+b: c
+c: b
+");
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "d", &[]), Ok(1));
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "d", &["b"]), Ok(0));
+
+        // a cycle through the end is cut off there, as paths end at `to`
+        let graph = adjacency("a: b
+b: a
+");
+        assert_eq!(count_paths::<_, _, u64>(&graph, "a", "b", &[]), Ok(1));
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let graph: HashMap<u32, Vec<u32>> = (0..50_000).map(|n| (n, vec![n + 1])).collect();
+        assert_eq!(count_paths::<_, _, u64>(&graph, &0, &50_000, &[&25_000]), Ok(1));
+    }
+
+    /// A ladder of diamonds doubles the number of paths with every rung.
+    fn ladder(rungs: u32) -> HashMap<u32, Vec<u32>> {
+        (0..rungs)
+            .flat_map(|n| {
+                let base = 3 * n;
+                [(base, vec![base + 1, base + 2]), (base + 1, vec![base + 3]), (base + 2, vec![base + 3])]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_count_types() {
+        let graph = ladder(70);
+        let end = 3 * 70;
+
+        assert_eq!(count_paths::<_, _, u64>(&graph, &0, &end, &[]), Err(PathError::Overflow));
+        assert_eq!(count_paths::<_, _, u128>(&graph, &0, &end, &[]), Ok(1 << 70));
+
+        let modular = count_paths::<_, _, Modular<1_000_000_007>>(&graph, &0, &end, &[]);
+        let expected = (1..=70).fold(1u64, |acc, _| acc * 2 % 1_000_000_007);
+        assert_eq!(modular, Ok(Modular(expected)));
+
+        let big = count_paths::<_, _, BigCount>(&graph, &0, &end, &[]).unwrap();
+        assert_eq!(big.to_string(), (1u128 << 70).to_string());
+        assert_eq!(BigCount::from(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(BigCount::zero().to_string(), "0");
+    }
+
+    fn graph_with(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            let (from, to) = (graph.intern(from), graph.intern(to));
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn labels(graph: &Graph, ids: &[usize]) -> Vec<String> {
+        ids.iter().map(|&id| graph.label(id).to_owned()).collect()
+    }
+
+    #[test]
+    fn test_parse_and_intern() {
+        let graph = Graph::parse("aaa: you hhh\nyou: bbb ccc\nbbb: out\n");
+
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.id("aaa"), Some(0));
+        assert_eq!(graph.id("zzz"), None);
+        assert_eq!(labels(&graph, graph.successors(graph.id("you").unwrap())), vec!["bbb", "ccc"]);
+        assert!(graph.successors(graph.id("out").unwrap()).is_empty());
+
+        let (you, out) = (graph.id("you").unwrap(), graph.id("out").unwrap());
+        assert_eq!(graph.count_paths::<u64>(you, out, &[]), Ok(1));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = graph_with(&[("c", "d"), ("a", "b"), ("b", "c"), ("a", "c")]);
+        let order = graph.topological_sort().unwrap();
+
+        let rank: Vec<usize> = (0..graph.len()).map(|n| order.iter().position(|&o| o == n).unwrap()).collect();
+        for from in 0..graph.len() {
+            for &to in graph.successors(from) {
+                assert!(rank[from] < rank[to]);
+            }
+        }
+
+        let cyclic = graph_with(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "b")]);
+        assert_eq!(labels(&cyclic, &cyclic.topological_sort().unwrap_err()), vec!["b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_reverse_and_reachability() {
+        let graph = graph_with(&[("a", "b"), ("b", "c"), ("d", "c")]);
+        let reachable = graph.reachable_from(graph.id("b").unwrap());
+        assert_eq!(reachable, vec![false, true, true, false]);
+
+        let reverse = graph.reverse();
+        assert_eq!(labels(&reverse, reverse.successors(reverse.id("c").unwrap())), vec!["b", "d"]);
+        assert_eq!(reverse.reachable_from(reverse.id("c").unwrap()), vec![true, true, true, true]);
+    }
+
+    #[test]
+    fn test_condensation() {
+        let graph = graph_with(&[("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "e"), ("e", "d"), ("f", "e")]);
+        let condensation = graph.condensation();
+
+        let mut components: Vec<Vec<String>> = condensation
+            .components
+            .iter()
+            .map(|members| {
+                let mut names = labels(&graph, members);
+                names.sort();
+                names
+            })
+            .collect();
+        components.sort();
+        assert_eq!(components, vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]);
+
+        let dag = &condensation.graph;
+        assert!(dag.topological_sort().is_ok());
+        assert_eq!(dag.topological_sort().unwrap(), (0..dag.len()).collect::<Vec<_>>());
+
+        let abc = condensation.component_of[graph.id("a").unwrap()];
+        let de = condensation.component_of[graph.id("d").unwrap()];
+        assert_eq!(dag.successors(abc), &[de]);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = graph_with(&[("a", "b")]);
+        assert_eq!(graph.to_dot(), "digraph {\n    \"a\";\n    \"b\";\n    \"a\" -> \"b\";\n}\n");
+    }
+}
@@ -0,0 +1,456 @@
+//! A small integer linear programming facade over `good_lp`.
+//!
+//! Models are built from typed rows independent of any solver; the backend is picked at compile time
+//! through the `ilp-highs` or `ilp-cbc` cargo features.
+use std::fmt::{Display, Write};
+
+#[cfg(all(feature = "ilp-highs", feature = "ilp-cbc"))]
+compile_error!("the `ilp-highs` and `ilp-cbc` features select different backends and cannot be combined. Use `--no-default-features --features ilp-cbc` for CBC.");
+
+/// Handle of a variable within a [`Model`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Var(usize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+    Continuous,
+    Integer,
+    Binary,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sense {
+    Minimise,
+    Maximise,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Le,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+struct VariableSpec {
+    name: String,
+    kind: Kind,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// A linear constraint `sum(coefficient * var) <comparison> rhs`.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub terms: Vec<(Var, f64)>,
+    pub comparison: Comparison,
+    pub rhs: f64,
+}
+
+impl Row {
+    pub fn eq(terms: Vec<(Var, f64)>, rhs: f64) -> Self {
+        Self { terms, comparison: Comparison::Eq, rhs }
+    }
+
+    pub fn le(terms: Vec<(Var, f64)>, rhs: f64) -> Self {
+        Self { terms, comparison: Comparison::Le, rhs }
+    }
+
+    pub fn ge(terms: Vec<(Var, f64)>, rhs: f64) -> Self {
+        Self { terms, comparison: Comparison::Ge, rhs }
+    }
+
+    /// Sums the given variables with coefficient 1, e.g. for "exactly one of" constraints.
+    pub fn sum_of(vars: impl IntoIterator<Item = Var>, comparison: Comparison, rhs: f64) -> Self {
+        Self {
+            terms: vars.into_iter().map(|var| (var, 1.0)).collect(),
+            comparison,
+            rhs,
+        }
+    }
+}
+
+/// Variable values of a solved model.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    values: Vec<f64>,
+    pub objective: f64,
+}
+
+impl Solution {
+    pub fn value(&self, var: Var) -> f64 {
+        self.values[var.0]
+    }
+}
+
+/// Result of [`Model::solve`].
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// The model has an objective and the solver proved this solution optimal.
+    Optimal(Solution),
+    /// The model has no objective and this solution satisfies all constraints.
+    Feasible(Solution),
+    Infeasible,
+    Error(String),
+}
+
+impl Outcome {
+    /// The solution of an optimal or feasible outcome.
+    pub fn solution(&self) -> Option<&Solution> {
+        match self {
+            Outcome::Optimal(solution) | Outcome::Feasible(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Model {
+    sense: Sense,
+    objective: Vec<(Var, f64)>,
+    variables: Vec<VariableSpec>,
+    rows: Vec<Row>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Model {
+    /// Creates a pure feasibility model; use [`Model::set_objective`] to optimise something.
+    pub fn new() -> Self {
+        Self {
+            sense: Sense::Minimise,
+            objective: vec![],
+            variables: vec![],
+            rows: vec![],
+        }
+    }
+
+    pub fn add_variable(&mut self, kind: Kind, min: Option<f64>, max: Option<f64>) -> Var {
+        let var = Var(self.variables.len());
+        self.variables.push(VariableSpec {
+            name: format!("x{}", var.0),
+            kind,
+            min,
+            max,
+        });
+        var
+    }
+
+    pub fn binary(&mut self) -> Var {
+        self.add_variable(Kind::Binary, Some(0.0), Some(1.0))
+    }
+
+    /// An integer variable bounded below by zero.
+    pub fn non_negative_integer(&mut self) -> Var {
+        self.add_variable(Kind::Integer, Some(0.0), None)
+    }
+
+    /// Renames a variable in the LP dump. Names must not contain whitespace.
+    pub fn set_name(&mut self, var: Var, name: &str) {
+        self.variables[var.0].name = name.to_string();
+    }
+
+    pub fn set_objective(&mut self, sense: Sense, terms: Vec<(Var, f64)>) {
+        self.sense = sense;
+        self.objective = terms;
+    }
+
+    pub fn add_row(&mut self, row: Row) {
+        self.rows.push(row);
+    }
+
+    pub fn variable_count(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn solve(&self) -> Outcome {
+        match backend::solve(self) {
+            Ok(values) => {
+                let objective = self
+                    .objective
+                    .iter()
+                    .map(|(var, coefficient)| coefficient * values[var.0])
+                    .sum();
+                let solution = Solution { values, objective };
+
+                if self.objective.is_empty() {
+                    Outcome::Feasible(solution)
+                } else {
+                    Outcome::Optimal(solution)
+                }
+            }
+            Err(backend::Failure::Infeasible) => Outcome::Infeasible,
+            Err(backend::Failure::Other(message)) => Outcome::Error(message),
+        }
+    }
+
+    /// Renders the model in CPLEX LP format, e.g. for inspection with `highs model.lp`.
+    pub fn to_lp(&self) -> String {
+        let mut lp = String::new();
+
+        let sense = match self.sense {
+            Sense::Minimise => "Minimize",
+            Sense::Maximise => "Maximize",
+        };
+        let _ = writeln!(lp, "{sense}");
+        if self.objective.is_empty() {
+            let _ = writeln!(lp, " obj:");
+        } else {
+            let _ = writeln!(lp, " obj: {}", self.format_terms(&self.objective));
+        }
+
+        let _ = writeln!(lp, "Subject To");
+        for (index, row) in self.rows.iter().enumerate() {
+            let comparison = match row.comparison {
+                Comparison::Eq => "=",
+                Comparison::Le => "<=",
+                Comparison::Ge => ">=",
+            };
+            let _ = writeln!(
+                lp,
+                " c{index}: {} {comparison} {}",
+                self.format_terms(&row.terms),
+                row.rhs
+            );
+        }
+
+        let bounds: Vec<String> = self
+            .variables
+            .iter()
+            .filter(|s| s.kind != Kind::Binary)
+            .map(|spec| match (spec.min, spec.max) {
+                (Some(min), Some(max)) => format!(" {min} <= {} <= {max}", spec.name),
+                (Some(min), None) => format!(" {} >= {min}", spec.name),
+                (None, Some(max)) => format!(" -inf <= {} <= {max}", spec.name),
+                (None, None) => format!(" {} free", spec.name),
+            })
+            .collect();
+
+        if !bounds.is_empty() {
+            let _ = writeln!(lp, "Bounds\n{}", bounds.join("\n"));
+        }
+
+        self.write_section(&mut lp, "General", Kind::Integer);
+        self.write_section(&mut lp, "Binary", Kind::Binary);
+
+        lp.push_str("End\n");
+        lp
+    }
+
+    fn format_terms(&self, terms: &[(Var, f64)]) -> String {
+        if terms.is_empty() {
+            return "0".into();
+        }
+
+        terms
+            .iter()
+            .enumerate()
+            .map(|(index, (var, coefficient))| {
+                let name = &self.variables[var.0].name;
+                let sign = if *coefficient < 0.0 { "-" } else { "+" };
+                let magnitude = coefficient.abs();
+                let term = if magnitude == 1.0 {
+                    name.to_string()
+                } else {
+                    format!("{magnitude} {name}")
+                };
+
+                match (index, sign) {
+                    (0, "+") => term,
+                    (0, _) => format!("- {term}"),
+                    _ => format!("{sign} {term}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn write_section(&self, lp: &mut String, title: &str, kind: Kind) {
+        let names: Vec<&str> = self
+            .variables
+            .iter()
+            .filter(|s| s.kind == kind)
+            .map(|s| s.name.as_str())
+            .collect();
+
+        if !names.is_empty() {
+            let _ = writeln!(lp, "{title}");
+            let _ = writeln!(lp, " {}", names.join(" "));
+        }
+    }
+}
+
+impl Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_lp())
+    }
+}
+
+#[cfg(any(feature = "ilp-highs", feature = "ilp-cbc"))]
+mod backend {
+    use super::{Comparison, Kind, Model, Sense};
+    use good_lp::{
+        Expression, ProblemVariables, ResolutionError, Solution, SolverModel, constraint, variable,
+    };
+
+    pub enum Failure {
+        Infeasible,
+        Other(String),
+    }
+
+    pub fn solve(model: &Model) -> Result<Vec<f64>, Failure> {
+        let mut problem = ProblemVariables::new();
+
+        let vars: Vec<_> = model
+            .variables
+            .iter()
+            .map(|spec| {
+                let mut definition = match spec.kind {
+                    Kind::Continuous => variable(),
+                    Kind::Integer => variable().integer(),
+                    Kind::Binary => variable().binary(),
+                };
+                if let Some(min) = spec.min {
+                    definition = definition.min(min);
+                }
+                if let Some(max) = spec.max {
+                    definition = definition.max(max);
+                }
+                problem.add(definition)
+            })
+            .collect();
+
+        let expression = |terms: &[(super::Var, f64)]| -> Expression {
+            terms
+                .iter()
+                .map(|(var, coefficient)| *coefficient * vars[var.0])
+                .sum()
+        };
+
+        let objective = expression(&model.objective);
+        let unsolved = match model.sense {
+            Sense::Minimise => problem.minimise(objective),
+            Sense::Maximise => problem.maximise(objective),
+        };
+
+        #[cfg(feature = "ilp-highs")]
+        let mut solver = unsolved.using(good_lp::highs);
+        #[cfg(feature = "ilp-cbc")]
+        let mut solver = unsolved.using(good_lp::coin_cbc);
+
+        for row in &model.rows {
+            let lhs = expression(&row.terms);
+            solver = solver.with(match row.comparison {
+                Comparison::Eq => constraint::eq(lhs, row.rhs),
+                Comparison::Le => constraint::leq(lhs, row.rhs),
+                Comparison::Ge => constraint::geq(lhs, row.rhs),
+            });
+        }
+
+        match solver.solve() {
+            Ok(solution) => Ok(vars.iter().map(|&var| solution.value(var)).collect()),
+            Err(ResolutionError::Infeasible) => Err(Failure::Infeasible),
+            Err(e) => Err(Failure::Other(e.to_string())),
+        }
+    }
+}
+
+#[cfg(not(any(feature = "ilp-highs", feature = "ilp-cbc")))]
+mod backend {
+    use super::Model;
+
+    pub enum Failure {
+        #[allow(dead_code)]
+        Infeasible,
+        Other(String),
+    }
+
+    pub fn solve(_model: &Model) -> Result<Vec<f64>, Failure> {
+        Err(Failure::Other(
+            "no ILP backend enabled, build with the `ilp-highs` or `ilp-cbc` feature.".into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn knapsack() -> (Model, Vec<Var>) {
+        let mut model = Model::new();
+        let vars: Vec<Var> = (0..3).map(|_| model.binary()).collect();
+
+        model.set_objective(
+            Sense::Maximise,
+            vec![(vars[0], 6.0), (vars[1], 10.0), (vars[2], 12.0)],
+        );
+        model.add_row(Row::le(
+            vec![(vars[0], 1.0), (vars[1], 2.0), (vars[2], 3.0)],
+            5.0,
+        ));
+
+        (model, vars)
+    }
+
+    #[test]
+    fn test_to_lp() {
+        let (mut model, vars) = knapsack();
+        let count = model.non_negative_integer();
+        model.set_name(count, "count");
+        model.add_row(Row::ge(vec![(count, 1.0), (vars[0], -2.0)], 0.0));
+
+        let expected = [
+            "Maximize",
+            " obj: 6 x0 + 10 x1 + 12 x2",
+            "Subject To",
+            " c0: x0 + 2 x1 + 3 x2 <= 5",
+            " c1: count - 2 x0 >= 0",
+            "Bounds",
+            " count >= 0",
+            "General",
+            " count",
+            "Binary",
+            " x0 x1 x2",
+            "End",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(model.to_lp(), expected);
+    }
+
+    #[test]
+    #[cfg(any(feature = "ilp-highs", feature = "ilp-cbc"))]
+    fn test_solve_optimal() {
+        let (model, vars) = knapsack();
+
+        let Outcome::Optimal(solution) = model.solve() else {
+            panic!("expected an optimal solution");
+        };
+        assert_eq!(solution.objective.round(), 22.0);
+        assert_eq!(solution.value(vars[0]).round(), 0.0);
+    }
+
+    #[test]
+    #[cfg(any(feature = "ilp-highs", feature = "ilp-cbc"))]
+    fn test_solve_feasibility() {
+        let mut model = Model::new();
+        let vars: Vec<Var> = (0..2).map(|_| model.binary()).collect();
+        model.add_row(Row::sum_of(vars.clone(), Comparison::Eq, 1.0));
+        assert!(matches!(model.solve(), Outcome::Feasible(_)));
+
+        model.add_row(Row::sum_of(vars, Comparison::Ge, 3.0));
+        assert!(matches!(model.solve(), Outcome::Infeasible));
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "ilp-highs", feature = "ilp-cbc")))]
+    fn test_solve_without_backend() {
+        let (model, _) = knapsack();
+        assert!(matches!(model.solve(), Outcome::Error(_)));
+    }
+}
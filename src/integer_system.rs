@@ -0,0 +1,488 @@
+//! Exact solver for small systems `A x = b` over the non-negative integers.
+//!
+//! The system is reduced with Gauss-Jordan elimination in exact arithmetic, which leaves every pivot variable
+//! expressed through the free ones. The free variables are then enumerated within bounds taken from the equations,
+//! so no floating-point rounding is involved at any step.
+
+use std::fmt::Display;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No non-negative integer assignment satisfies all equations.
+    NoSolution,
+    /// A free variable has no finite upper bound, so it cannot be enumerated.
+    Unbounded(usize),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::NoSolution => write!(f, "system has no non-negative integer solution."),
+            SolveError::Unbounded(var) => write!(f, "free variable x{var} is not bounded by any equation."),
+        }
+    }
+}
+
+/// A system of linear equations with integer coefficients whose variables are non-negative integers.
+#[derive(Debug, Clone)]
+pub struct IntegerSystem {
+    variables: usize,
+    rows: Vec<(Vec<i64>, i64)>,
+}
+
+/// A reduced row `pivot * x[var] + sum(free[i] * x[free_vars[i]]) = rhs`, with `pivot > 0`.
+#[derive(Debug)]
+struct Reduced {
+    var: usize,
+    pivot: i128,
+    free: Vec<i128>,
+    rhs: i128,
+}
+
+impl IntegerSystem {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            rows: vec![],
+        }
+    }
+
+    /// Adds the equation `sum(coefficient * x[var]) = rhs`.
+    pub fn add_equation(&mut self, terms: &[(usize, i64)], rhs: i64) {
+        let mut coefficients = vec![0; self.variables];
+        for &(var, coefficient) in terms {
+            coefficients[var] += coefficient;
+        }
+        self.rows.push((coefficients, rhs));
+    }
+
+    /// Finds a solution with the smallest sum of all variables.
+    pub fn minimise_sum(&self) -> Result<Vec<u64>, SolveError> {
+        let (reduced, free_vars) = self.reduce().ok_or(SolveError::NoSolution)?;
+        let bounds = self.upper_bounds();
+
+        let free_bounds = free_vars
+            .iter()
+            .map(|&var| bounds[var].ok_or(SolveError::Unbounded(var)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let pivot_bounds: Vec<Option<u64>> = reduced.iter().map(|row| bounds[row.var]).collect();
+        let mut search = Search::new(&reduced, free_bounds, &pivot_bounds);
+        search.run(0);
+
+        let free_values = search.best.ok_or(SolveError::NoSolution)?.1;
+
+        let mut solution = vec![0; self.variables];
+        for (&var, &value) in free_vars.iter().zip(&free_values) {
+            solution[var] = value;
+        }
+        for row in &reduced {
+            let numerator = row.rhs
+                - row
+                    .free
+                    .iter()
+                    .zip(&free_values)
+                    .map(|(&c, &value)| c * i128::from(value))
+                    .sum::<i128>();
+            solution[row.var] = (numerator / row.pivot) as u64;
+        }
+        Ok(solution)
+    }
+
+    /// Brings the system into reduced row echelon form. Rows are kept integral by cross-multiplying and dividing
+    /// out their gcd, which is exact rational elimination without a separate fraction type.
+    /// Returns `None` if the system is inconsistent.
+    fn reduce(&self) -> Option<(Vec<Reduced>, Vec<usize>)> {
+        let mut rows: Vec<Vec<i128>> = self
+            .rows
+            .iter()
+            .map(|(coefficients, rhs)| {
+                coefficients
+                    .iter()
+                    .chain([rhs])
+                    .map(|&c| i128::from(c))
+                    .collect()
+            })
+            .collect();
+        let mut pivots: Vec<usize> = vec![];
+
+        for column in 0..self.variables {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|&r| rows[r][column] != 0) else {
+                continue;
+            };
+            rows.swap(rank, found);
+
+            let pivot_row = rows[rank].clone();
+            for (index, row) in rows.iter_mut().enumerate() {
+                let factor = row[column];
+                if index == rank || factor == 0 {
+                    continue;
+                }
+                for (value, &pivot_value) in row.iter_mut().zip(&pivot_row) {
+                    *value = *value * pivot_row[column] - pivot_value * factor;
+                }
+                normalise(row);
+            }
+
+            pivots.push(column);
+        }
+
+        if rows[pivots.len()..].iter().any(|row| row[self.variables] != 0) {
+            return None;
+        }
+
+        let free_vars: Vec<usize> = (0..self.variables)
+            .filter(|column| !pivots.contains(column))
+            .collect();
+
+        let reduced = pivots
+            .iter()
+            .enumerate()
+            .map(|(rank, &var)| {
+                let mut row = rows[rank].clone();
+                if row[var] < 0 {
+                    row.iter_mut().for_each(|value| *value = -*value);
+                }
+                Reduced {
+                    var,
+                    pivot: row[var],
+                    free: free_vars.iter().map(|&free| row[free]).collect(),
+                    rhs: row[self.variables],
+                }
+            })
+            .collect();
+
+        Some((reduced, free_vars))
+    }
+
+    /// Upper bound of every variable implied by equations whose coefficients are all non-negative.
+    fn upper_bounds(&self) -> Vec<Option<u64>> {
+        let mut bounds = vec![None; self.variables];
+
+        for (coefficients, rhs) in &self.rows {
+            if coefficients.iter().any(|&c| c < 0) {
+                continue;
+            }
+            for (var, &coefficient) in coefficients.iter().enumerate() {
+                if coefficient > 0 {
+                    let bound = (*rhs).max(0) as u64 / coefficient as u64;
+                    bounds[var] = Some(bounds[var].map_or(bound, |current: u64| current.min(bound)));
+                }
+            }
+        }
+
+        // a variable that appears in no equation is best left at 0.
+        for (var, bound) in bounds.iter_mut().enumerate() {
+            if self.rows.iter().all(|(coefficients, _)| coefficients[var] == 0) {
+                *bound = Some(0);
+            }
+        }
+
+        bounds
+    }
+}
+
+/// Divides a row by the gcd of its entries.
+fn normalise(row: &mut [i128]) {
+    let divisor = row.iter().fold(0, |acc, &value| gcd(acc, value.abs()));
+    if divisor > 1 {
+        row.iter_mut().for_each(|value| *value /= divisor);
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Branch and bound over the free variables.
+///
+/// With `L` the lcm of all pivots, `L * sum(x)` is an integer affine function `objective + sum(weights[i] * free[i])`
+/// of the free variables. A branch is cut once the smallest objective its unassigned variables could still reach
+/// is no better than the best solution so far. The domain of each free variable is narrowed so that every pivot
+/// variable can still end up between zero and its own upper bound.
+struct Search<'a> {
+    reduced: &'a [Reduced],
+    free_bounds: Vec<u64>,
+    weights: Vec<i128>,
+    /// Smallest contribution of the free variables from `depth` onwards to the scaled objective.
+    weight_slack: Vec<i128>,
+    /// Range of `sum(free[i] * x[i])` over the free variables from `depth` onwards, per row.
+    reach: Vec<Vec<(i128, i128)>>,
+    /// Depth from which each row no longer depends on unassigned variables.
+    determined: Vec<usize>,
+    /// Largest value of `pivot * x[var]` for each row.
+    caps: Vec<i128>,
+    /// Right-hand side of each row minus the assigned free variables.
+    residuals: Vec<i128>,
+    objective: i128,
+    free_values: Vec<u64>,
+    best: Option<(i128, Vec<u64>)>,
+}
+
+impl<'a> Search<'a> {
+    fn new(reduced: &'a [Reduced], free_bounds: Vec<u64>, pivot_bounds: &[Option<u64>]) -> Self {
+        let scale = reduced.iter().fold(1, |acc, row| acc / gcd(acc, row.pivot) * row.pivot);
+        let free_count = free_bounds.len();
+
+        let weights: Vec<i128> = (0..free_count)
+            .map(|i| scale - reduced.iter().map(|row| scale / row.pivot * row.free[i]).sum::<i128>())
+            .collect();
+
+        let mut weight_slack = vec![0; free_count + 1];
+        let mut reach = vec![vec![(0, 0); free_count + 1]; reduced.len()];
+        for i in (0..free_count).rev() {
+            let bound = i128::from(free_bounds[i]);
+            weight_slack[i] = weight_slack[i + 1] + weights[i].min(0) * bound;
+            for (row, range) in reduced.iter().zip(reach.iter_mut()) {
+                let (min, max) = range[i + 1];
+                range[i] = (min + row.free[i].min(0) * bound, max + row.free[i].max(0) * bound);
+            }
+        }
+
+        Self {
+            reduced,
+            weights,
+            weight_slack,
+            reach,
+            determined: reduced
+                .iter()
+                .map(|row| row.free.iter().rposition(|&c| c != 0).map_or(0, |last| last + 1))
+                .collect(),
+            caps: reduced
+                .iter()
+                .zip(pivot_bounds)
+                .map(|(row, bound)| bound.map_or(i128::MAX / 4, |bound| row.pivot * i128::from(bound)))
+                .collect(),
+            residuals: reduced.iter().map(|row| row.rhs).collect(),
+            objective: reduced.iter().map(|row| scale / row.pivot * row.rhs).sum(),
+            free_values: vec![0; free_count],
+            free_bounds,
+            best: None,
+        }
+    }
+
+    fn is_bounded_by_best(&self, depth: usize) -> bool {
+        let lower_bound = self.objective + self.weight_slack[depth];
+        self.best.as_ref().is_some_and(|(best, _)| lower_bound >= *best)
+    }
+
+    fn rows_satisfiable(&self, depth: usize) -> bool {
+        (0..self.reduced.len()).all(|index| {
+            let residual = self.residuals[index];
+            let (min, max) = self.reach[index][depth];
+            let integral = depth < self.determined[index] || residual % self.reduced[index].pivot == 0;
+            integral && residual - min >= 0 && residual - max <= self.caps[index]
+        })
+    }
+
+    /// Values of the free variable at `depth` that keep every row satisfiable.
+    fn domain(&self, depth: usize) -> Option<(u64, u64)> {
+        let (mut lo, mut hi) = (0, i128::from(self.free_bounds[depth]));
+
+        for (index, row) in self.reduced.iter().enumerate() {
+            let coefficient = row.free[depth];
+            if coefficient == 0 {
+                continue;
+            }
+
+            // coefficient * x must lie within [residual - max - cap, residual - min]
+            let residual = self.residuals[index];
+            let (min, max) = self.reach[index][depth + 1];
+            let (low, high) = (residual - max - self.caps[index], residual - min);
+
+            if coefficient > 0 {
+                lo = lo.max(ceil_div(low, coefficient));
+                hi = hi.min(floor_div(high, coefficient));
+            } else {
+                lo = lo.max(ceil_div(high, coefficient));
+                hi = hi.min(floor_div(low, coefficient));
+            }
+        }
+
+        (lo <= hi).then_some((lo as u64, hi as u64))
+    }
+
+    fn run(&mut self, depth: usize) {
+        if self.is_bounded_by_best(depth) || !self.rows_satisfiable(depth) {
+            return;
+        }
+
+        if depth == self.free_values.len() {
+            self.best = Some((self.objective, self.free_values.clone()));
+            return;
+        }
+
+        let Some((lo, hi)) = self.domain(depth) else {
+            return;
+        };
+
+        // walk values in the direction that increases the objective, so the bound check can stop the loop early
+        let values: Box<dyn Iterator<Item = u64>> = if self.weights[depth] >= 0 {
+            Box::new(lo..=hi)
+        } else {
+            Box::new((lo..=hi).rev())
+        };
+
+        for value in values {
+            self.assign(depth, value);
+            if self.is_bounded_by_best(depth + 1) {
+                break;
+            }
+            self.run(depth + 1);
+        }
+        self.assign(depth, 0);
+    }
+
+    fn assign(&mut self, depth: usize, value: u64) {
+        let delta = i128::from(value) - i128::from(self.free_values[depth]);
+        self.free_values[depth] = value;
+        self.objective += self.weights[depth] * delta;
+        for (row, residual) in self.reduced.iter().zip(self.residuals.iter_mut()) {
+            *residual -= row.free[depth] * delta;
+        }
+    }
+}
+
+fn floor_div(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) { quotient - 1 } else { quotient }
+}
+
+fn ceil_div(a: i128, b: i128) -> i128 {
+    -floor_div(-a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::Rng;
+
+    fn system_from_buttons(buttons: &[&[usize]], targets: &[i64]) -> IntegerSystem {
+        let mut system = IntegerSystem::new(buttons.len());
+        for (counter, &target) in targets.iter().enumerate() {
+            let terms: Vec<(usize, i64)> = (0..buttons.len())
+                .filter(|&b| buttons[b].contains(&counter))
+                .map(|b| (b, 1))
+                .collect();
+            system.add_equation(&terms, target);
+        }
+        system
+    }
+
+    fn total(solution: &[u64]) -> u64 {
+        solution.iter().sum()
+    }
+
+    #[test]
+    fn test_minimise_sum() {
+        let system = system_from_buttons(&[&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]], &[3, 5, 4, 7]);
+        assert_eq!(system.minimise_sum().map(|s| total(&s)), Ok(10));
+
+        let system = system_from_buttons(
+            &[&[0, 2, 3, 4], &[2, 3], &[0, 4], &[0, 1, 2], &[1, 2, 3, 4]],
+            &[7, 5, 12, 7, 2],
+        );
+        assert_eq!(system.minimise_sum().map(|s| total(&s)), Ok(12));
+
+        let system = system_from_buttons(
+            &[&[0, 1, 2, 3, 4], &[0, 3, 4], &[0, 1, 2, 4, 5], &[1, 2]],
+            &[10, 11, 11, 5, 10, 5],
+        );
+        assert_eq!(system.minimise_sum().map(|s| total(&s)), Ok(11));
+    }
+
+    #[test]
+    fn test_fractional_pivots() {
+        // 2a + b = 3, a + 2b = 3: the only rational solution is a = b = 1
+        let mut system = IntegerSystem::new(2);
+        system.add_equation(&[(0, 2), (1, 1)], 3);
+        system.add_equation(&[(0, 1), (1, 2)], 3);
+        assert_eq!(system.minimise_sum(), Ok(vec![1, 1]));
+
+        // 2a = 3 has no integer solution
+        let mut system = IntegerSystem::new(1);
+        system.add_equation(&[(0, 2)], 3);
+        assert_eq!(system.minimise_sum(), Err(SolveError::NoSolution));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut system = IntegerSystem::new(2);
+        system.add_equation(&[(0, 1), (1, 1)], 1);
+        system.add_equation(&[(0, 1), (1, 1)], 2);
+        assert_eq!(system.minimise_sum(), Err(SolveError::NoSolution));
+
+        let mut system = IntegerSystem::new(2);
+        system.add_equation(&[(0, 1), (1, -1)], 1);
+        assert_eq!(system.minimise_sum(), Err(SolveError::Unbounded(1)));
+    }
+
+    #[test]
+    fn test_unused_variable() {
+        // x1 has a zero column, e.g. a button that affects no counter.
+        let mut system = IntegerSystem::new(3);
+        system.add_equation(&[(0, 1), (2, 1)], 2);
+        system.add_equation(&[(2, 1)], 1);
+        assert_eq!(system.minimise_sum(), Ok(vec![1, 0, 1]));
+
+        assert_eq!(IntegerSystem::new(2).minimise_sum(), Ok(vec![0, 0]));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..200 {
+            let variables = 1 + rng.next(4) as usize;
+            let equations = 1 + rng.next(4) as usize;
+
+            let coefficients: Vec<Vec<i64>> = (0..equations)
+                .map(|_| (0..variables).map(|_| rng.next(3)).collect())
+                .collect();
+            let hidden: Vec<i64> = (0..variables).map(|_| rng.next(4)).collect();
+
+            let mut system = IntegerSystem::new(variables);
+            for row in &coefficients {
+                let terms: Vec<(usize, i64)> = row.iter().copied().enumerate().collect();
+                let rhs = row.iter().zip(&hidden).map(|(c, x)| c * x).sum();
+                system.add_equation(&terms, rhs);
+            }
+
+            let bounds = system.upper_bounds();
+            let Ok(solution) = system.minimise_sum() else {
+                // a variable that appears in no equation is unbounded
+                assert!(bounds.iter().any(Option::is_none));
+                continue;
+            };
+
+            let satisfies = |x: &[i64]| {
+                coefficients
+                    .iter()
+                    .zip(&system.rows)
+                    .all(|(row, (_, rhs))| row.iter().zip(x).map(|(c, v)| c * v).sum::<i64>() == *rhs)
+            };
+
+            let as_signed: Vec<i64> = solution.iter().map(|&v| v as i64).collect();
+            assert!(satisfies(&as_signed));
+
+            let mut best = u64::MAX;
+            let mut candidate = vec![0i64; variables];
+            'enumerate: loop {
+                if satisfies(&candidate) {
+                    best = best.min(candidate.iter().sum::<i64>() as u64);
+                }
+                for (var, value) in candidate.iter_mut().enumerate() {
+                    if (*value as u64) < bounds[var].unwrap() {
+                        *value += 1;
+                        continue 'enumerate;
+                    }
+                    *value = 0;
+                }
+                break;
+            }
+
+            assert_eq!(total(&solution), best);
+        }
+    }
+}
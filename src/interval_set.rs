@@ -0,0 +1,256 @@
+use std::cmp::{max, min};
+
+/// Integer types that can be used as bounds of an [`IntervalSet`].
+pub trait Discrete: Copy + Ord {
+    /// The next value, or `None` on overflow.
+    fn succ(self) -> Option<Self>;
+    /// The previous value, or `None` on underflow.
+    fn pred(self) -> Option<Self>;
+    /// Number of values in the inclusive range `start..=end`.
+    fn width(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn width(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers stored as sorted, disjoint and non-adjacent inclusive ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Discrete> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// Adds the inclusive range `start..=end`, merging it with any overlapping or adjacent ranges.
+    /// Empty ranges (`start > end`) are ignored.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        // first range that overlaps or touches the new one on the left
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.succ().is_some_and(|next| next < start));
+        // first range that lies strictly to the right without touching
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| end.succ().is_none_or(|next| s <= next));
+
+        let (mut new_start, mut new_end) = (start, end);
+        if first < last {
+            new_start = min(new_start, self.ranges[first].0);
+            new_end = max(new_end, self.ranges[last - 1].1);
+        }
+
+        self.ranges.splice(first..last, [(new_start, new_end)]);
+    }
+
+    /// Checks whether `value` is covered by the set in `O(log n)`.
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// The merged ranges in ascending order.
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.ranges.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Total number of values covered by the set.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(|&(s, e)| T::width(s, e)).sum()
+    }
+
+    /// The uncovered ranges between consecutive ranges of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.ranges.windows(2).filter_map(|pair| {
+            let start = pair[0].1.succ()?;
+            let end = pair[1].0.pred()?;
+            Some((start, end))
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &(start, end) in &other.ranges {
+            result.insert(start, end);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a_start, a_end) = self.ranges[i];
+            let (b_start, b_end) = other.ranges[j];
+
+            let start = max(a_start, b_start);
+            let end = min(a_end, b_end);
+            if start <= end {
+                ranges.push((start, end));
+            }
+
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for &(start, end) in &self.ranges {
+            let mut current = Some(start);
+
+            while j < other.ranges.len() && other.ranges[j].1 < start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let Some(from) = current {
+                match other.ranges.get(k) {
+                    Some(&(cut_start, cut_end)) if cut_start <= end => {
+                        if from < cut_start {
+                            ranges.push((from, cut_start.pred().unwrap()));
+                        }
+                        current = if cut_end < end { cut_end.succ() } else { None };
+                        k += 1;
+                    }
+                    _ => {
+                        ranges.push((from, end));
+                        current = None;
+                    }
+                }
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl<T: Discrete> FromIterator<(T, T)> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for (start, end) in iter {
+            set.insert(start, end);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet<i64> {
+        ranges.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let result = set(&[(1, 1), (3, 5), (4, 6), (5, 10)]);
+        assert_eq!(result.ranges(), &[(1, 1), (3, 10)]);
+
+        let result = set(&[(5, 10), (1, 2), (3, 4), (20, 30), (12, 12)]);
+        assert_eq!(result.ranges(), &[(1, 10), (12, 12), (20, 30)]);
+
+        let result = set(&[(1, 3), (7, 9), (2, 8)]);
+        assert_eq!(result.ranges(), &[(1, 9)]);
+
+        let result = set(&[(5, 1)]);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_insert_at_type_limits() {
+        let mut result: IntervalSet<u64> = IntervalSet::new();
+        result.insert(u64::MAX - 1, u64::MAX);
+        result.insert(0, 1);
+        result.insert(2, u64::MAX - 2);
+        assert_eq!(result.ranges(), &[(0, u64::MAX)]);
+        assert_eq!(result.len(), u64::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn test_contains() {
+        let result = set(&[(1, 5), (7, 10)]);
+        let matches: Vec<i64> = (0..=11).filter(|&v| result.contains(v)).collect();
+        assert_eq!(matches, vec![1, 2, 3, 4, 5, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_len_and_gaps() {
+        let result = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert_eq!(result.len(), 14);
+        assert_eq!(result.gaps().collect::<Vec<_>>(), vec![(6, 9)]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 50)]);
+
+        assert_eq!(a.union(&b).ranges(), &[(1, 30), (40, 50)]);
+        assert_eq!(a.intersection(&b).ranges(), &[(5, 10), (20, 25)]);
+        assert_eq!(a.difference(&b).ranges(), &[(1, 4), (26, 30)]);
+        assert_eq!(b.difference(&a).ranges(), &[(11, 19), (40, 50)]);
+    }
+
+    #[test]
+    fn test_set_operations_match_membership() {
+        let a = set(&[(0, 3), (6, 6), (9, 14), (18, 19)]);
+        let b = set(&[(2, 7), (11, 11), (13, 20)]);
+
+        for v in -1..=21 {
+            assert_eq!(a.union(&b).contains(v), a.contains(v) || b.contains(v));
+            assert_eq!(a.intersection(&b).contains(v), a.contains(v) && b.contains(v));
+            assert_eq!(a.difference(&b).contains(v), a.contains(v) && !b.contains(v));
+        }
+    }
+}
@@ -1,3 +1,18 @@
 pub mod template;
+pub mod digits;
 pub mod dlx;
+pub mod geometry;
+pub mod graph;
+pub mod gf2;
+pub mod ilp;
+pub mod integer_system;
+pub mod interval_set;
+pub mod modular_counter;
+pub mod parse;
+pub mod patterns;
+pub mod repeated_digits;
+pub mod text_block;
+
+#[cfg(test)]
+mod test_rng;
 // Use this file to add helper functions and additional modules.
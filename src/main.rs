@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, report, scaffold, solve, time};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -8,6 +8,7 @@ use std::process;
 
 mod args {
     use advent_of_code::template::Day;
+    use advent_of_code::template::commands::time::{DEFAULT_REGRESSION_THRESHOLD, HeapStats};
     use std::process;
 
     pub enum AppArguments {
@@ -21,12 +22,18 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            return_type: Option<String>,
+            examples: Option<u8>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            allocs: bool,
             submit: Option<u8>,
+            force: bool,
+            watch: bool,
         },
         All {
             release: bool,
@@ -35,6 +42,12 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            heap_stats: HeapStats,
+        },
+        Report {
+            markdown: bool,
+            output: Option<String>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -50,13 +63,24 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let heap_stats = heap_stats(&mut args)?;
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    // `--threshold` implies `--compare`.
+                    compare: (compare || threshold.is_some())
+                        .then(|| threshold.unwrap_or(DEFAULT_REGRESSION_THRESHOLD)),
+                    heap_stats,
                 }
             }
+            Some("report") => AppArguments::Report {
+                markdown: args.contains("--markdown"),
+                output: args.opt_value_from_str("--output")?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -67,13 +91,23 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                return_type: args.opt_value_from_str("--return-type")?,
+                examples: args.opt_value_from_str("--examples")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let heap_stats = heap_stats(&mut args)?;
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat: heap_stats == HeapStats::Profiled,
+                    allocs: heap_stats == HeapStats::Counted,
+                    force: args.contains("--force"),
+                    watch: args.contains("--watch"),
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -93,6 +127,16 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// `--dhat` and `--allocs` both replace the global allocator, so they cannot be combined.
+    fn heap_stats(args: &mut pico_args::Arguments) -> Result<HeapStats, Box<dyn std::error::Error>> {
+        match (args.contains("--dhat"), args.contains("--allocs")) {
+            (true, true) => Err("--dhat and --allocs cannot be combined, as both replace the global allocator.".into()),
+            (true, false) => Ok(HeapStats::Profiled),
+            (false, true) => Ok(HeapStats::Counted),
+            (false, false) => Ok(HeapStats::Off),
+        }
+    }
 }
 
 fn main() {
@@ -103,31 +147,45 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                heap_stats,
+            } => time::handle(day, all, store, compare, heap_stats),
+            AppArguments::Report { markdown, output } => report::handle(markdown, output.as_deref()),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
+                template,
+                return_type,
+                examples,
+            } => scaffold::handle(
+                day,
+                overwrite,
+                template.as_deref().unwrap_or("default"),
+                return_type.as_deref(),
+                examples.unwrap_or(1),
+                download,
+            ),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                allocs,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                force,
+                watch,
+            } => solve::handle(day, release, dhat, allocs, submit, force, watch),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        scaffold::handle(day, false, "default", None, 1, true);
                         read::handle(day)
                     }
                     None => {
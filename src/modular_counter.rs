@@ -0,0 +1,151 @@
+/// A position on a circular dial of `modulus` slots, moved by signed amounts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ModularCounter {
+    modulus: i64,
+    position: i64,
+}
+
+/// Totals over a batch of moves, see [`ModularCounter::apply_all`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// Moves that ended on the target.
+    pub landings: u64,
+    /// Times the target was reached, counting every slot passed on the way.
+    pub crossings: u64,
+}
+
+impl ModularCounter {
+    pub fn new(modulus: i64, start: i64) -> Self {
+        assert!(modulus > 0, "modulus must be positive, got {modulus}");
+        Self {
+            modulus,
+            position: start.rem_euclid(modulus),
+        }
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    pub fn modulus(&self) -> i64 {
+        self.modulus
+    }
+
+    /// How often a move by `amount` reaches `target`, one slot at a time, without moving.
+    /// The starting slot does not count, the final one does.
+    pub fn crossings(&self, amount: i64, target: i64) -> u64 {
+        // number of slots k in the unwrapped range of the move with k ≡ target (mod modulus)
+        let below = |k: i64| (k - target).div_euclid(self.modulus);
+        let end = self.position + amount;
+
+        let count = if amount >= 0 {
+            below(end) - below(self.position)
+        } else {
+            below(self.position - 1) - below(end - 1)
+        };
+        count as u64
+    }
+
+    /// Moves by `amount`, returning how often `target` was crossed on the way.
+    pub fn apply(&mut self, amount: i64, target: i64) -> u64 {
+        let crossings = self.crossings(amount, target);
+        self.position = (self.position + amount).rem_euclid(self.modulus);
+        crossings
+    }
+
+    /// Applies all moves in order, counting landings on and crossings of `target`.
+    pub fn apply_all(&mut self, moves: impl IntoIterator<Item = i64>, target: i64) -> Tally {
+        let target_slot = target.rem_euclid(self.modulus);
+        let mut tally = Tally::default();
+
+        for amount in moves {
+            tally.crossings += self.apply(amount, target);
+            if self.position == target_slot {
+                tally.landings += 1;
+            }
+        }
+
+        tally
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation that turns the dial one slot at a time.
+    fn simulate(modulus: i64, start: i64, amount: i64, target: i64) -> (i64, u64) {
+        let mut position = start;
+        let mut crossings = 0;
+
+        for _ in 0..amount.abs() {
+            position = (position + amount.signum()).rem_euclid(modulus);
+            if position == target.rem_euclid(modulus) {
+                crossings += 1;
+            }
+        }
+
+        (position, crossings)
+    }
+
+    #[test]
+    fn test_crossings() {
+        let mut counter = ModularCounter::new(100, 14);
+        assert_eq!(counter.apply(-82, 0), 1);
+        assert_eq!(counter.position(), 32);
+
+        let mut counter = ModularCounter::new(100, 80);
+        assert_eq!(counter.apply(-687, 0), 7);
+        assert_eq!(counter.position(), 93);
+
+        let counter = ModularCounter::new(100, 0);
+        assert_eq!(counter.crossings(0, 0), 0);
+        assert_eq!(counter.crossings(100, 0), 1);
+        assert_eq!(counter.crossings(-100, 0), 1);
+        assert_eq!(counter.crossings(-1, 99), 1);
+    }
+
+    #[test]
+    fn test_matches_simulation() {
+        for modulus in 1..=7 {
+            for start in 0..modulus {
+                for target in -modulus..2 * modulus {
+                    for amount in -3 * modulus - 2..=3 * modulus + 2 {
+                        let mut counter = ModularCounter::new(modulus, start);
+                        let crossings = counter.apply(amount, target);
+
+                        assert_eq!(
+                            (counter.position(), crossings),
+                            simulate(modulus, start, amount, target),
+                            "modulus {modulus}, start {start}, amount {amount}, target {target}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_all() {
+        let moves = [-68, -30, 48, -5, 60, -55, -1, -99, 14, -82];
+        let tally = ModularCounter::new(100, 50).apply_all(moves, 0);
+        assert_eq!(tally, Tally { landings: 3, crossings: 6 });
+
+        for target in 0..10 {
+            let mut counter = ModularCounter::new(10, 3);
+            let tally = counter.apply_all(moves, target);
+
+            let mut position = 3;
+            let mut expected = Tally::default();
+            for amount in moves {
+                let (next, crossings) = simulate(10, position, amount, target);
+                position = next;
+                expected.crossings += crossings;
+                expected.landings += u64::from(position == target);
+            }
+
+            assert_eq!(tally, expected);
+            assert_eq!(counter.position(), position);
+        }
+    }
+}
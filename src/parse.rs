@@ -0,0 +1,372 @@
+//! Typed parsers for the usual puzzle input shapes.
+//!
+//! Everything works on [`Span`]s, slices of the input that remember where they start, so a malformed value is
+//! reported with its line and column instead of silently turning into a default.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending text.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A piece of the input together with its starting position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A byte offset into a span with its line and column, used to locate consecutive pieces without rescanning.
+#[derive(Debug, Copy, Clone)]
+struct Cursor {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a> Span<'a> {
+    /// The whole input, starting at line 1, column 1.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// An error pointing at the start of this span.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    /// The sub-span starting at byte `start` and ending at byte `end`.
+    pub fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        self.slice_from(self.advance(self.cursor(), start), end)
+    }
+
+    fn cursor(&self) -> Cursor {
+        Cursor {
+            offset: 0,
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    /// Moves `cursor` forward to byte `offset`, only scanning the text in between.
+    fn advance(&self, cursor: Cursor, offset: usize) -> Cursor {
+        let between = &self.text[cursor.offset..offset];
+        match between.rfind('\n') {
+            None => Cursor {
+                offset,
+                column: cursor.column + between.chars().count(),
+                ..cursor
+            },
+            Some(newline) => Cursor {
+                offset,
+                line: cursor.line + between.matches('\n').count(),
+                column: between[newline + 1..].chars().count() + 1,
+            },
+        }
+    }
+
+    fn slice_from(&self, cursor: Cursor, end: usize) -> Span<'a> {
+        Span {
+            text: &self.text[cursor.offset..end],
+            line: cursor.line,
+            column: cursor.column,
+        }
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+        self.slice(start, end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Splits on any of `separators`, keeping empty pieces.
+    pub fn split(&self, separators: &[char]) -> Vec<Span<'a>> {
+        let mut pieces = vec![];
+        let mut cursor = self.cursor();
+
+        for (index, c) in self.text.char_indices() {
+            if separators.contains(&c) {
+                pieces.push(self.slice_from(cursor, index));
+                cursor = self.advance(cursor, index + c.len_utf8());
+            }
+        }
+        pieces.push(self.slice_from(cursor, self.text.len()));
+
+        pieces
+    }
+
+    /// Splits on the first occurrence of `separator`.
+    pub fn split_once(&self, separator: char) -> Option<(Span<'a>, Span<'a>)> {
+        let index = self.text.find(separator)?;
+        Some((self.slice(0, index), self.slice(index + separator.len_utf8(), self.text.len())))
+    }
+
+    /// The lines of the span, without line endings.
+    pub fn lines(&self) -> Vec<Span<'a>> {
+        let mut lines = self.split(&['\n']);
+        if lines.last().is_some_and(|line| line.text.is_empty()) {
+            lines.pop();
+        }
+        for line in &mut lines {
+            if let Some(text) = line.text.strip_suffix('\r') {
+                line.text = text;
+            }
+        }
+        lines
+    }
+
+    /// Groups of lines separated by blank lines, blank lines at either end dropped.
+    pub fn sections(&self) -> Vec<Span<'a>> {
+        let lines = self.lines();
+        let mut sections = vec![];
+        let mut current: Option<(Span<'a>, Span<'a>)> = None;
+
+        for line in lines {
+            if line.is_empty() {
+                sections.extend(current.take().map(|(first, last)| self.join(first, last)));
+            } else {
+                current = Some(current.map_or((line, line), |(first, _)| (first, line)));
+            }
+        }
+        sections.extend(current.map(|(first, last)| self.join(first, last)));
+
+        sections
+    }
+
+    /// The sub-span from the start of `first` to the end of `last`, both of which must lie within `self`.
+    fn join(&self, first: Span<'a>, last: Span<'a>) -> Span<'a> {
+        let offset = |span: &Span| span.text.as_ptr() as usize - self.text.as_ptr() as usize;
+        Span {
+            text: &self.text[offset(&first)..offset(&last) + last.text.len()],
+            ..first
+        }
+    }
+
+    /// Whitespace-separated words.
+    pub fn words(&self) -> Vec<Span<'a>> {
+        self.split(&[' ', '\t'])
+            .into_iter()
+            .filter(|word| !word.text.is_empty())
+            .collect()
+    }
+
+    /// Parses the trimmed span as a single value.
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        let trimmed = self.trim();
+        trimmed.text.parse().map_err(|_| {
+            trimmed.error(format!(
+                "expected {}, found {:?}",
+                std::any::type_name::<T>(),
+                trimmed.text
+            ))
+        })
+    }
+
+    /// Parses values separated by any of `separators`. Empty fields, e.g. from repeated spaces, are skipped.
+    pub fn integers<T: FromStr>(&self, separators: &[char]) -> Result<Vec<T>, ParseError> {
+        self.split(separators)
+            .into_iter()
+            .filter(|field| !field.is_empty())
+            .map(|field| field.parse())
+            .collect()
+    }
+
+    /// Parses exactly `N` values separated by any of `separators`.
+    pub fn integer_array<T: FromStr, const N: usize>(&self, separators: &[char]) -> Result<[T; N], ParseError> {
+        let values: Vec<T> = self.integers(separators)?;
+        let count = values.len();
+        values
+            .try_into()
+            .map_err(|_| self.trim().error(format!("expected {N} values, found {count}")))
+    }
+
+    /// Parses an inclusive range written as `start-end`. A leading `-` belongs to the start value.
+    pub fn range<T: FromStr>(&self) -> Result<(T, T), ParseError> {
+        let trimmed = self.trim();
+        let dash = trimmed
+            .text
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-')
+            .map(|(index, _)| index)
+            .ok_or_else(|| trimmed.error(format!("expected a range like `a-b`, found {:?}", trimmed.text)))?;
+
+        let start = trimmed.slice(0, dash).parse()?;
+        let end = trimmed.slice(dash + 1, trimmed.text.len()).parse()?;
+        Ok((start, end))
+    }
+
+    /// Splits a `key: a b c` line into the key and its whitespace-separated values.
+    pub fn key_values(&self) -> Result<(Span<'a>, Vec<Span<'a>>), ParseError> {
+        let (key, values) = self
+            .split_once(':')
+            .ok_or_else(|| self.trim().error("expected `key: values`"))?;
+        Ok((key.trim(), values.words()))
+    }
+
+    /// Parses a rectangular grid, mapping every character with `cell`.
+    pub fn grid<T>(&self, cell: impl Fn(char) -> Option<T>) -> Result<Vec<Vec<T>>, ParseError> {
+        let mut rows: Vec<Vec<T>> = vec![];
+
+        for line in self.lines() {
+            let row = line
+                .text
+                .char_indices()
+                .map(|(index, c)| {
+                    cell(c).ok_or_else(|| line.slice(index, index).error(format!("unexpected character {c:?}")))
+                })
+                .collect::<Result<Vec<T>, _>>()?;
+
+            if let Some(first) = rows.first()
+                && first.len() != row.len()
+            {
+                return Err(line.error(format!("expected {} columns, found {}", first.len(), row.len())));
+            }
+            rows.push(row);
+        }
+
+        Ok(rows)
+    }
+}
+
+/// Parses every non-empty line of `input` with `parse_line`.
+pub fn lines<'a, T>(
+    input: impl Into<Span<'a>>,
+    parse_line: impl FnMut(Span<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .into()
+        .lines()
+        .into_iter()
+        .filter(|line| !line.is_empty())
+        .map(parse_line)
+        .collect()
+}
+
+/// Prints a parse error to stderr and discards it, for solutions that return an `Option`.
+pub fn report<T>(result: Result<T, ParseError>) -> Option<T> {
+    result.map_err(|error| eprintln!("invalid input: {error}")).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        let span = Span::new("  3  -4,5\t6 ");
+        assert_eq!(span.integers::<i32>(&[' ', ',', '\t']), Ok(vec![3, -4, 5, 6]));
+        assert_eq!(Span::new("1,2,3").integer_array::<u8, 3>(&[',']), Ok([1, 2, 3]));
+
+        let error = Span::new("1,2").integer_array::<u8, 3>(&[',']).unwrap_err();
+        assert_eq!(error.message, "expected 3 values, found 2");
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let input = "1,2,3\n4,x,6\n";
+        let error = lines(input, |line| line.integers::<u32>(&[','])).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.to_string(), "line 2, column 3: expected u32, found \"x\"");
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(Span::new("11-22").range::<u64>(), Ok((11, 22)));
+        assert_eq!(Span::new("-5--2").range::<i64>(), Ok((-5, -2)));
+
+        let error = Span::new("12").range::<u64>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = Span::new("ab, 3-4x").split(&[','])[1].range::<u64>().unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (7, "expected u64, found \"4x\""));
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "3-5\n10-14\n\n\n1\n5\r\n8\n";
+        let sections = Span::new(input).sections();
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].text, "3-5\n10-14");
+        assert_eq!(sections[1].line, 5);
+        assert_eq!(lines(sections[1], |line| line.parse::<u8>()), Ok(vec![1, 5, 8]));
+
+        let error = sections[1].lines()[1].slice(1, 1).error("here");
+        assert_eq!((error.line, error.column), (6, 2));
+    }
+
+    #[test]
+    fn test_positions_of_long_input() {
+        let mut input: String = (0..10_000).map(|i| format!("{i},{i}\n\n")).collect();
+        input.push_str("1,x\n");
+
+        let error = lines(input.as_str(), |line| line.integers::<u32>(&[','])).unwrap_err();
+        assert_eq!((error.line, error.column), (20_001, 3));
+
+        let sections = Span::new(&input).sections();
+        assert_eq!(sections.len(), 10_001);
+        assert_eq!((sections[9_999].line, sections[9_999].text), (19_999, "9999,9999"));
+        assert_eq!(sections[10_000].split(&[','])[1].column, 3);
+    }
+
+    #[test]
+    fn test_key_values() {
+        let (key, values) = Span::new("aaa: you  hhh").key_values().unwrap();
+        assert_eq!(key.text, "aaa");
+        assert_eq!(values.iter().map(|v| v.text).collect::<Vec<_>>(), vec!["you", "hhh"]);
+        assert_eq!(values[1].column, 11);
+
+        assert!(Span::new("no colon").key_values().is_err());
+    }
+
+    #[test]
+    fn test_grid() {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let grid = Span::new("#.\n.#\n").grid(cell).unwrap();
+        assert_eq!(grid, vec![vec![true, false], vec![false, true]]);
+
+        let error = Span::new("#.\n.x\n").grid(cell).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = Span::new("#.\n.\n").grid(cell).unwrap_err();
+        assert_eq!(error.message, "expected 2 columns, found 1");
+    }
+}
@@ -0,0 +1,178 @@
+//! Regexes compiled once per process, and typed access to their capture groups.
+//!
+//! Declare a pattern with [`static_regex!`](crate::static_regex) where it is used, then read the groups with
+//! [`captures`] or [`extract`]. Failures are [`ParseError`]s pointing into the input, like the rest of
+//! [`parse`](crate::parse).
+
+use crate::parse::{ParseError, Span};
+use std::str::FromStr;
+
+pub use regex::{Captures, Regex};
+
+/// Compiles a regex literal on first use and returns the same `&'static Regex` on every later call.
+///
+/// ```ignore
+/// let fields = patterns::captures(static_regex!(r"^(\d+)x(\d+)$"), line)?;
+/// ```
+#[macro_export]
+macro_rules! static_regex {
+    ($pattern:literal) => {{
+        static REGEX: std::sync::OnceLock<$crate::patterns::Regex> = std::sync::OnceLock::new();
+        REGEX.get_or_init(|| $crate::patterns::Regex::new($pattern).expect("invalid static regex"))
+    }};
+}
+
+/// The capture groups of one match, positioned within the input they were found in.
+#[derive(Debug)]
+pub struct Fields<'a> {
+    input: Span<'a>,
+    captures: Captures<'a>,
+}
+
+/// Types that can be built from the capture groups of a match.
+///
+/// Tuples of up to six [`FromStr`] values read groups `1..=n` in order.
+pub trait FromCaptures: Sized {
+    fn from_captures(fields: &Fields<'_>) -> Result<Self, ParseError>;
+}
+
+impl<'a> Fields<'a> {
+    /// The whole match.
+    pub fn matched(&self) -> Span<'a> {
+        let whole = self.captures.get_match();
+        self.input.slice(whole.start(), whole.end())
+    }
+
+    /// The text of a numbered group, failing at the start of the match if the group did not participate.
+    pub fn span(&self, group: usize) -> Result<Span<'a>, ParseError> {
+        let found = self
+            .captures
+            .get(group)
+            .ok_or_else(|| self.matched().error(format!("capture group {group} did not match")))?;
+        Ok(self.input.slice(found.start(), found.end()))
+    }
+
+    /// The text of a named group.
+    pub fn named(&self, name: &str) -> Result<Span<'a>, ParseError> {
+        let found = self
+            .captures
+            .name(name)
+            .ok_or_else(|| self.matched().error(format!("capture group `{name}` did not match")))?;
+        Ok(self.input.slice(found.start(), found.end()))
+    }
+
+    /// Parses a numbered group.
+    pub fn get<T: FromStr>(&self, group: usize) -> Result<T, ParseError> {
+        self.span(group)?.parse()
+    }
+}
+
+/// Matches `regex` against `input`, failing at the start of `input` if it does not match.
+pub fn captures<'a>(regex: &Regex, input: impl Into<Span<'a>>) -> Result<Fields<'a>, ParseError> {
+    let input = input.into();
+    let captures = regex.captures(input.text).ok_or_else(|| {
+        input.trim().error(format!(
+            "expected text matching `{}`, found {:?}",
+            regex.as_str(),
+            input.text.trim()
+        ))
+    })?;
+
+    Ok(Fields { input, captures })
+}
+
+/// All non-overlapping matches of `regex` in `input`.
+pub fn captures_iter<'a>(regex: &Regex, input: impl Into<Span<'a>>) -> impl Iterator<Item = Fields<'a>> {
+    let input = input.into();
+    regex
+        .captures_iter(input.text)
+        .map(move |captures| Fields { input, captures })
+}
+
+/// Matches `regex` against `input` and converts the groups into `T`.
+pub fn extract<'a, T: FromCaptures>(regex: &Regex, input: impl Into<Span<'a>>) -> Result<T, ParseError> {
+    T::from_captures(&captures(regex, input)?)
+}
+
+macro_rules! tuple_from_captures {
+    ($($name:ident $group:literal),+) => {
+        impl<$($name: FromStr),+> FromCaptures for ($($name,)+) {
+            fn from_captures(fields: &Fields<'_>) -> Result<Self, ParseError> {
+                Ok(($(fields.get::<$name>($group)?,)+))
+            }
+        }
+    };
+}
+
+tuple_from_captures!(A 1);
+tuple_from_captures!(A 1, B 2);
+tuple_from_captures!(A 1, B 2, C 3);
+tuple_from_captures!(A 1, B 2, C 3, D 4);
+tuple_from_captures!(A 1, B 2, C 3, D 4, E 5);
+tuple_from_captures!(A 1, B 2, C 3, D 4, E 5, F 6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Region {
+        width: usize,
+        height: usize,
+        counts: Vec<u32>,
+    }
+
+    impl FromCaptures for Region {
+        fn from_captures(fields: &Fields<'_>) -> Result<Self, ParseError> {
+            Ok(Region {
+                width: fields.get(1)?,
+                height: fields.get(2)?,
+                counts: fields.named("counts")?.integers(&[' '])?,
+            })
+        }
+    }
+
+    fn region_regex() -> &'static Regex {
+        crate::static_regex!(r"^(\d+)x(\d+): (?<counts>.+)$")
+    }
+
+    #[test]
+    fn test_static_regex_is_compiled_once() {
+        assert!(std::ptr::eq(region_regex(), region_regex()));
+    }
+
+    #[test]
+    fn test_extract() {
+        let (width, height): (u8, u8) = extract(crate::static_regex!(r"(\d+)x(\d+)"), "size 4x12").unwrap();
+        assert_eq!((width, height), (4, 12));
+
+        let region: Region = extract(region_regex(), "12x5: 1 0 3").unwrap();
+        assert_eq!(region, Region { width: 12, height: 5, counts: vec![1, 0, 3] });
+    }
+
+    #[test]
+    fn test_errors() {
+        let lines = Span::new("4x4: 0 2\n  4y4: 1\n4x4: 0 z\n").lines();
+
+        let error = extract::<Region>(region_regex(), lines[1]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert!(error.message.starts_with("expected text matching"));
+
+        let error = extract::<Region>(region_regex(), lines[2]).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 8));
+
+        let error = extract::<(u8, u8)>(crate::static_regex!(r"(\d+)(x)?"), "7").unwrap_err();
+        assert_eq!(error.message, "capture group 2 did not match");
+    }
+
+    #[test]
+    fn test_captures_iter() {
+        let line = Span::new("[.#] (3) (1,3) {3,5}");
+        let buttons: Vec<Vec<u8>> = captures_iter(crate::static_regex!(r"\(([^)]+)\)"), line)
+            .map(|fields| fields.span(1)?.integers(&[',']))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(buttons, vec![vec![3], vec![1, 3]]);
+    }
+}
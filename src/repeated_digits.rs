@@ -0,0 +1,180 @@
+use std::cmp::{max, min};
+
+/// Longest decimal representation of a `u64`.
+const MAX_DIGITS: u32 = 20;
+
+fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
+
+/// Factor that turns a block into the block repeated `repetitions` times, e.g. `12 * 10101 = 121212`.
+fn repeat_multiplier(block_len: u32, repetitions: u32) -> u128 {
+    (pow10(block_len * repetitions) - 1) / (pow10(block_len) - 1)
+}
+
+/// Returns the first and last block (without leading zeroes) whose repetition lies within `[lo, hi]`,
+/// together with the multiplier for that repetition.
+fn block_bounds(block_len: u32, repetitions: u32, lo: u64, hi: u64) -> Option<(u128, u128, u128)> {
+    if block_len == 0 || repetitions == 0 || block_len * repetitions > MAX_DIGITS || lo > hi {
+        return None;
+    }
+
+    let multiplier = repeat_multiplier(block_len, repetitions);
+    let first = max(pow10(block_len - 1), (lo as u128).div_ceil(multiplier));
+    let last = min(pow10(block_len) - 1, hi as u128 / multiplier);
+
+    (first <= last).then_some((first, last, multiplier))
+}
+
+/// Iterates all numbers in `[lo, hi]` made of a `block_len`-digit block repeated `repetitions` times.
+pub fn repeated_numbers(
+    block_len: u32,
+    repetitions: u32,
+    lo: u64,
+    hi: u64,
+) -> impl DoubleEndedIterator<Item = u64> {
+    let (first, last, multiplier) =
+        block_bounds(block_len, repetitions, lo, hi).unwrap_or((1, 0, 0));
+    (first..=last).map(move |block| (block * multiplier) as u64)
+}
+
+/// Sums all numbers in `[lo, hi]` made of a `block_len`-digit block repeated `repetitions` times.
+pub fn sum_repeated(block_len: u32, repetitions: u32, lo: u64, hi: u64) -> u128 {
+    match block_bounds(block_len, repetitions, lo, hi) {
+        Some((first, last, multiplier)) => multiplier * (first + last) * (last - first + 1) / 2,
+        None => 0,
+    }
+}
+
+fn distinct_prime_factors(mut n: u32) -> Vec<u32> {
+    let mut factors = vec![];
+    let mut p = 2;
+
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+
+    if n > 1 {
+        factors.push(n);
+    }
+
+    factors
+}
+
+/// Sums all numbers in `[lo, hi]` consisting of some block repeated at least twice.
+///
+/// A number that repeats with several block lengths (e.g. `222222` as `2`, `22` and `222`) is counted once:
+/// for each digit length, the sets of numbers with period `len / p` for the primes `p` dividing `len`
+/// are combined by inclusion–exclusion.
+pub fn sum_periodic(lo: u64, hi: u64) -> u128 {
+    let mut sum: i128 = 0;
+
+    for total_len in 2..=MAX_DIGITS {
+        let primes = distinct_prime_factors(total_len);
+
+        for subset in 1..(1u32 << primes.len()) {
+            let divisor: u32 = primes
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| subset & (1 << i) != 0)
+                .map(|(_, p)| p)
+                .product();
+
+            let block_len = total_len / divisor;
+            let part = sum_repeated(block_len, divisor, lo, hi) as i128;
+
+            if subset.count_ones() % 2 == 1 {
+                sum += part;
+            } else {
+                sum -= part;
+            }
+        }
+    }
+
+    sum as u128
+}
+
+fn digit_len(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// Checks whether `n` is a block repeated exactly `repetitions` times.
+pub fn is_repeated(n: u64, repetitions: u32) -> bool {
+    let len = digit_len(n);
+    if repetitions < 2 || !len.is_multiple_of(repetitions) {
+        return false;
+    }
+
+    let block_len = len / repetitions;
+    let multiplier = repeat_multiplier(block_len, repetitions);
+    let n = n as u128;
+
+    n.is_multiple_of(multiplier) && n / multiplier >= pow10(block_len - 1)
+}
+
+/// Checks whether `n` is some block repeated at least twice.
+pub fn is_periodic(n: u64) -> bool {
+    (2..=digit_len(n)).any(|repetitions| is_repeated(n, repetitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_numbers() {
+        assert_eq!(repeated_numbers(1, 2, 11, 22).collect::<Vec<_>>(), vec![11, 22]);
+        assert_eq!(repeated_numbers(1, 2, 95, 115).collect::<Vec<_>>(), vec![99]);
+        assert_eq!(repeated_numbers(1, 3, 95, 115).collect::<Vec<_>>(), vec![111]);
+        assert_eq!(repeated_numbers(2, 2, 998, 1012).collect::<Vec<_>>(), vec![1010]);
+        assert_eq!(repeated_numbers(3, 2, 1698522, 1698528).count(), 0);
+        assert_eq!(repeated_numbers(0, 2, 0, 100).count(), 0);
+    }
+
+    #[test]
+    fn test_sum_repeated_matches_enumeration() {
+        let cases = [(1, 2, 0, 1000), (2, 3, 100_000, 999_999), (5, 2, 1_188_511_880, 1_188_511_890)];
+
+        for (block_len, repetitions, lo, hi) in cases {
+            let expected: u128 = repeated_numbers(block_len, repetitions, lo, hi)
+                .map(u128::from)
+                .sum();
+            assert_eq!(sum_repeated(block_len, repetitions, lo, hi), expected);
+        }
+    }
+
+    #[test]
+    fn test_sum_periodic_matches_brute_force() {
+        let cases = [(1, 10_000), (95, 115), (998, 1012), (565_653, 565_659), (824_824_821, 824_824_827)];
+
+        for (lo, hi) in cases {
+            let expected: u128 = (lo..=hi).filter(|&n| is_periodic(n)).map(u128::from).sum();
+            assert_eq!(sum_periodic(lo, hi), expected, "range {lo}-{hi}");
+        }
+    }
+
+    #[test]
+    fn test_large_ranges() {
+        let blocks: u128 = (10_000..=99_999).sum();
+        assert_eq!(sum_repeated(5, 2, 0, 10_000_000_000), blocks * 100_001);
+        assert_eq!(repeated_numbers(10, 2, 0, u64::MAX).next_back(), Some(18_446_744_071_844_674_407));
+        assert!(sum_periodic(1, u64::MAX) > 0);
+    }
+
+    #[test]
+    fn test_is_repeated() {
+        assert!(is_repeated(1010, 2));
+        assert!(is_repeated(222222, 3));
+        assert!(!is_repeated(101, 3));
+        assert!(!is_repeated(7, 1));
+        assert!(is_periodic(824824824));
+        assert!(!is_periodic(1698522));
+        assert!(!is_periodic(0));
+    }
+}
@@ -0,0 +1,111 @@
+/// A global allocator that counts allocations, installed by `solution!` with the `count-allocs` feature.
+/// It is much cheaper than dhat, so the counts can be collected during regular timed runs.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::template::runner::MemoryStats;
+
+#[cfg(all(feature = "count-allocs", feature = "dhat-heap"))]
+compile_error!("the `count-allocs` and `dhat-heap` features both install a global allocator and cannot be combined.");
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+static BASELINE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Forwards to the system allocator while keeping count.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // a reallocation counts as one allocation of the new size, like in dhat.
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Start counting from zero. Bytes that are live at this point do not count towards the peak.
+pub fn reset() {
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    TOTAL_BYTES.store(0, Ordering::Relaxed);
+    PEAK_BYTES.store(live, Ordering::Relaxed);
+    BASELINE_BYTES.store(live, Ordering::Relaxed);
+}
+
+/// The counts since the last [`reset`].
+pub fn stats() -> MemoryStats {
+    MemoryStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE_BYTES.load(Ordering::Relaxed)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CountingAlloc, reset, stats};
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        // the test binary does not use this allocator, so only calls made here are counted.
+        reset();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        unsafe {
+            let a = CountingAlloc.alloc(layout);
+            let b = CountingAlloc.alloc_zeroed(layout);
+            CountingAlloc.dealloc(a, layout);
+            let b = CountingAlloc.realloc(b, layout, 128);
+            CountingAlloc.dealloc(b, Layout::from_size_align(128, 8).unwrap());
+        }
+
+        let stats = stats();
+        assert_eq!((stats.allocations, stats.total_bytes, stats.peak_bytes), (3, 256, 128));
+    }
+}
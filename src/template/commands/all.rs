@@ -1,5 +1,6 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::all_days;
+use crate::template::run_multi::{HeapStats, run_multi};
 
 pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+    run_multi(&all_days().collect(), is_release, false, HeapStats::Off);
 }
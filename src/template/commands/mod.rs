@@ -1,6 +1,7 @@
 pub mod all;
 pub mod download;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
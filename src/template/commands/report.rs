@@ -0,0 +1,44 @@
+use std::{fs, process};
+
+use crate::template::aoc_cli;
+use crate::template::history::{self, History};
+use crate::template::report::{ReportMeta, render_html, render_markdown};
+use crate::template::timings::Timings;
+
+static HTML_REPORT_PATH: &str = "./data/report.html";
+
+/// Render stored timings as HTML to `output` (default: `data/report.html`), or as Markdown to `output` or stdout.
+pub fn handle(markdown: bool, output: Option<&str>) {
+    let timings = Timings::read_from_file();
+
+    if timings.data.is_empty() {
+        eprintln!("No stored timings found. Run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let history = History::read_from_file();
+    let meta = ReportMeta {
+        year: aoc_cli::get_year(),
+        commit: history.latest_commit().map(String::from),
+        generated_at: history::now(),
+    };
+
+    if markdown {
+        let report = render_markdown(&timings, &history, &meta);
+        match output {
+            Some(path) => write(path, &report),
+            None => print!("{report}"),
+        }
+    } else {
+        let report = render_html(&timings, &history, &meta);
+        write(output.unwrap_or(HTML_REPORT_PATH), &report);
+    }
+}
+
+fn write(path: &str, report: &str) {
+    if let Err(e) = fs::write(path, report) {
+        eprintln!("Failed to write report to {path}: {e}");
+        process::exit(1);
+    }
+    println!("Wrote report to {path}.");
+}
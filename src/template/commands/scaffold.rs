@@ -1,14 +1,55 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::commands::download;
+use crate::template::puzzle::{self, Answer, PuzzleExamples};
+use crate::template::{Day, aoc_cli};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Templates that ship with the repository. Templates in `templates/<name>.txt` take precedence.
+const BUILTIN_TEMPLATES: [(&str, &str); 5] = [
+    ("default", MODULE_TEMPLATE),
+    (
+        "empty",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/scaffolds/empty.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/scaffolds/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/scaffolds/graph.txt"
+        )),
+    ),
+    (
+        "parse-lines",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/scaffolds/parse-lines.txt"
+        )),
+    ),
+];
+
+const USER_TEMPLATE_DIR: &str = "templates";
+
+/// Used unless `--return-type` is given or an example answer is not a number.
+const DEFAULT_RETURN_TYPE: &str = "u64";
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -19,19 +60,189 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Create an empty file, keeping an existing one. Returns `false` if the file existed.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Load a template by name, looking in `<root>/templates` before the built-in templates.
+fn load_template(root: &Path, name: &str) -> Result<String, String> {
+    if let Ok(template) =
+        fs::read_to_string(root.join(USER_TEMPLATE_DIR).join(format!("{name}.txt")))
+    {
+        return Ok(template);
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            format!(
+                "Unknown template \"{name}\". Available templates: {}.",
+                template_names(root).join(", ")
+            )
+        })
+}
+
+fn template_names(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(root.join(USER_TEMPLATE_DIR)) {
+        names.extend(entries.filter_map(Result::ok).filter_map(|entry| {
+            let path = entry.path();
+            let is_template = path.extension().is_some_and(|ext| ext == "txt");
+            is_template.then(|| path.file_stem()?.to_str().map(String::from))?
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Values for the placeholders of a template.
+struct Placeholders {
+    day: Day,
+    year: Option<u16>,
+    /// Falls back to `Day N` until the puzzle is downloaded.
+    title: Option<String>,
+    return_type: String,
+    examples: PuzzleExamples,
+}
+
+impl Placeholders {
+    /// Values for `day`, taken from its puzzle description if it has been downloaded.
+    fn read(day: Day, return_type: Option<&str>) -> Self {
+        let puzzle = puzzle::read_puzzle(day);
+        let examples: PuzzleExamples = puzzle.as_deref().map(puzzle::examples).unwrap_or_default();
+        let return_type = return_type.map_or_else(|| default_return_type(&examples), String::from);
+
+        Self {
+            day,
+            year: aoc_cli::get_year(),
+            title: puzzle.as_deref().and_then(puzzle::title),
+            return_type,
+            examples,
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        let day_number = self.day.into_inner().to_string();
+        let title = self
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {day_number}"));
+        let header = match self.year {
+            Some(year) => format!("{title} (https://adventofcode.com/{year}/day/{day_number})"),
+            None => title.clone(),
+        };
+        let [part_one, part_two] = &self.examples.answers;
+
+        template
+            .replace("%HEADER%", &header)
+            .replace("%DAY_NUMBER%", &day_number)
+            .replace(
+                "%YEAR%",
+                &self.year.map(|year| year.to_string()).unwrap_or_default(),
+            )
+            .replace("%TITLE%", &title)
+            .replace("%RETURN_TYPE%", &self.return_type)
+            .replace(
+                "%PART_ONE_ANSWER%",
+                &answer_literal(part_one.as_ref(), &self.return_type),
+            )
+            .replace(
+                "%PART_TWO_ANSWER%",
+                &answer_literal(part_two.as_ref(), &self.return_type),
+            )
+            .replace(
+                "%PART_TWO_EXAMPLE%",
+                &match part_two {
+                    Some(answer) if answer.example > 0 => {
+                        format!("read_file_part(\"examples\", DAY, {})", answer.example + 1)
+                    }
+                    _ => "read_file(\"examples\", DAY)".to_string(),
+                },
+            )
+    }
+}
+
+fn is_number(answer: &Answer) -> bool {
+    answer.value.parse::<i128>().is_ok()
+}
+
+/// `String` if an example answer is not a number, e.g. a list of letters.
+fn default_return_type(examples: &PuzzleExamples) -> String {
+    let all_numbers = examples.answers.iter().flatten().all(is_number);
+    if all_numbers {
+        DEFAULT_RETURN_TYPE
+    } else {
+        "String"
+    }
+    .to_string()
+}
+
+/// The expected value of a test assert, e.g. `Some(42)`. An answer that does not fit the return type is left as a
+/// comment, so that the module still compiles.
+fn answer_literal(answer: Option<&Answer>, return_type: &str) -> String {
+    match answer {
+        None => "None".to_string(),
+        Some(answer) if return_type == "String" => {
+            format!("Some(String::from({:?}))", answer.value)
+        }
+        Some(answer) if is_number(answer) => format!("Some({})", answer.value),
+        Some(answer) => format!("None /* expected {:?} */", answer.value),
+    }
+}
+
+fn example_path(day: Day, index: usize, extension: &str) -> String {
+    if index == 0 {
+        format!("data/examples/{day}.{extension}")
+    } else {
+        format!("data/examples/{day}-{}.{extension}", index + 1)
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
+/// Write an example from the puzzle description, unless the file already has contents.
+fn write_example(path: &str, input: Option<&str>) -> Result<bool, std::io::Error> {
+    let is_empty = fs::read_to_string(path).map_or(true, |contents| contents.is_empty());
+    match input {
+        Some(input) if is_empty => fs::write(path, input).map(|()| true),
+        _ => create_file(path),
+    }
+}
+
+/// Scaffold the module, input and example files of `day`. With `download`, the puzzle is downloaded afterwards,
+/// so that a failed download leaves a usable scaffold, and the module is rendered again with its title and examples.
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    template: &str,
+    return_type: Option<&str>,
+    examples: u8,
+    download: bool,
+) {
     let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
+    let template = match load_template(Path::new("."), template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let placeholders = Placeholders::read(day, return_type);
+
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
         Err(e) => {
@@ -40,11 +251,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(placeholders.render(&template).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -55,25 +262,207 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    write_examples(day, examples, &placeholders.examples);
+
+    if download {
+        download::handle(day);
+
+        let placeholders = Placeholders::read(day, return_type);
+        if placeholders.title.is_some() {
+            if let Err(e) = fs::write(&module_path, placeholders.render(&template)) {
+                eprintln!("Failed to write module contents: {e}");
+                process::exit(1);
+            }
+            println!("Filled in the puzzle title and examples in \"{module_path}\"");
+            write_examples(day, examples, &placeholders.examples);
         }
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/// Create `count` example files, or one per example found in the puzzle, and record the answers found with them.
+fn write_examples(day: Day, count: u8, extracted: &PuzzleExamples) {
+    let count = usize::from(count).max(extracted.inputs.len()).max(1);
+
+    for index in 0..count {
+        let path = example_path(day, index, "txt");
+        let input = extracted.inputs.get(index).map(String::as_str);
+
+        match write_example(&path, input) {
+            Ok(true) if input.is_some() => {
+                println!("Created example file \"{path}\" from the puzzle description");
+            }
+            Ok(true) => {
+                println!("Created empty example file \"{path}\"");
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    for index in 0..extracted.inputs.len() {
+        let answers: String = (1..=2)
+            .zip(&extracted.answers)
+            .filter_map(|(part, answer)| {
+                let answer = answer.as_ref().filter(|answer| answer.example == index)?;
+                Some(format!("part_{part}: {}\n", answer.value))
+            })
+            .collect();
+
+        if answers.is_empty() {
+            continue;
+        }
+
+        let path = example_path(day, index, "answers");
+        match safe_create_file(&path, false).and_then(|mut file| file.write_all(answers.as_bytes()))
+        {
+            Ok(()) => {
+                println!("Created example answers file \"{path}\"");
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                eprintln!("Failed to create example answers file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTIN_TEMPLATES, Placeholders, default_return_type, load_template};
+    use crate::day;
+    use crate::template::puzzle::{Answer, PuzzleExamples};
+    use std::{env, fs};
+
+    fn placeholders(title: Option<&str>, examples: PuzzleExamples) -> Placeholders {
+        Placeholders {
+            day: day!(5),
+            year: Some(2025),
+            title: title.map(String::from),
+            return_type: "usize".into(),
+            examples,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "//! %TITLE% (%YEAR%)\nadvent_of_code::solution!(%DAY_NUMBER%);\nfn f() -> Option<%RETURN_TYPE%> {}";
+
+        assert_eq!(
+            placeholders(Some("Day 5: Cafeteria"), PuzzleExamples::default()).render(template),
+            "//! Day 5: Cafeteria (2025)\nadvent_of_code::solution!(5);\nfn f() -> Option<usize> {}"
+        );
+        assert!(
+            placeholders(None, PuzzleExamples::default())
+                .render(template)
+                .starts_with("//! Day 5 (2025)\n")
+        );
+    }
+
+    #[test]
+    fn renders_example_answers() {
+        let template = "%PART_ONE_ANSWER% %PART_TWO_ANSWER% %PART_TWO_EXAMPLE%";
+
+        assert_eq!(
+            placeholders(None, PuzzleExamples::default()).render(template),
+            "None None read_file(\"examples\", DAY)"
+        );
+
+        let examples = PuzzleExamples {
+            inputs: vec!["a\n".into(), "b\n".into()],
+            answers: [
+                Some(Answer {
+                    example: 0,
+                    value: "4277556".into(),
+                }),
+                Some(Answer {
+                    example: 1,
+                    value: "abc".into(),
+                }),
+            ],
+        };
+        assert_eq!(default_return_type(&examples), "String");
+        assert_eq!(
+            placeholders(None, examples.clone()).render(template),
+            "Some(4277556) None /* expected \"abc\" */ read_file_part(\"examples\", DAY, 2)"
+        );
+
+        let placeholders = Placeholders {
+            return_type: default_return_type(&examples),
+            ..placeholders(None, examples)
+        };
+        assert_eq!(
+            placeholders.render(template),
+            "Some(String::from(\"4277556\")) Some(String::from(\"abc\")) read_file_part(\"examples\", DAY, 2)"
+        );
+        assert_eq!(default_return_type(&PuzzleExamples::default()), "u64");
+    }
+
+    #[test]
+    fn renders_header_without_year() {
+        let template = "//! %HEADER%";
+
+        assert_eq!(
+            placeholders(Some("Day 5: Cafeteria"), PuzzleExamples::default()).render(template),
+            "//! Day 5: Cafeteria (https://adventofcode.com/2025/day/5)"
+        );
+
+        let placeholders = Placeholders {
+            year: None,
+            ..placeholders(None, PuzzleExamples::default())
+        };
+        assert_eq!(placeholders.render(template), "//! Day 5");
+    }
+
+    #[test]
+    fn builtin_templates_use_known_placeholders() {
+        for (name, template) in BUILTIN_TEMPLATES {
+            let rendered = placeholders(None, PuzzleExamples::default()).render(template);
+            assert!(
+                !rendered.contains('%'),
+                "template {name} has an unknown placeholder"
+            );
+            assert!(rendered.contains("advent_of_code::solution!(5);"));
+        }
+    }
+
+    #[test]
+    fn loads_user_templates_first() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("templates")).unwrap();
+        fs::write(root.join("templates/grid.txt"), "custom grid").unwrap();
+        fs::write(root.join("templates/dp.txt"), "custom dp").unwrap();
+
+        assert_eq!(load_template(&root, "grid").unwrap(), "custom grid");
+        assert_eq!(load_template(&root, "dp").unwrap(), "custom dp");
+        assert!(
+            load_template(&root, "graph")
+                .unwrap()
+                .contains("Graph::parse")
+        );
+        assert_eq!(
+            load_template(&root, "nope").unwrap_err(),
+            "Unknown template \"nope\". Available templates: default, dp, empty, graph, grid, parse-lines."
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,8 +1,93 @@
-use std::process::{Command, Stdio};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::watch::Watcher;
+use crate::template::{Day, examples};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+#[allow(clippy::fn_params_excessive_bools)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    allocs: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    watch: bool,
+) {
+    let cmd_args = cargo_args(day, release, dhat, allocs);
+
+    if !watch {
+        let examples_passed = run_examples(day, &cmd_args);
+
+        if let Some(part) = submit_part.filter(|_| !examples_passed && !force) {
+            eprintln!("Examples failed, not submitting part {part}. Pass --force to submit anyway.");
+            process::exit(1);
+        }
+
+        run_solution(&cmd_args, submit_part);
+        return;
+    }
+
+    if submit_part.is_some() {
+        eprintln!("The --submit option cannot be combined with --watch.");
+        process::exit(1);
+    }
+
+    let mut watcher = Watcher::new(".", day);
+
+    loop {
+        let _ = clear_screen(&mut io::stdout());
+        run_tests(day);
+        run_examples(day, &cmd_args);
+        run_solution(&cmd_args, None);
+
+        println!("\nWatching for changes to day {day}...");
+        watcher.wait();
+    }
+}
+
+/// Clear the terminal. Flushed right away, as the runs below write to the inherited stdout directly.
+fn clear_screen(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(ANSI_CLEAR_SCREEN.as_bytes())?;
+    out.flush()
+}
+
+fn run_tests(day: Day) {
+    spawn(&["test", "--quiet", "--bin", &day.to_string()]);
+}
+
+/// Check the solution against the examples with recorded answers. Returns `true` if there are none.
+fn run_examples(day: Day, cmd_args: &[String]) -> bool {
+    match examples::read_examples(Path::new("."), day) {
+        Ok(examples) if examples.is_empty() => return true,
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Failed to read example answers: {e}");
+            return false;
+        }
+    }
+
+    let mut args = cmd_args.to_vec();
+    args.push("--examples".to_string());
+    spawn(&args)
+}
+
+fn run_solution(cmd_args: &[String], submit_part: Option<u8>) {
+    let mut args = cmd_args.to_vec();
+
+    if let Some(submit_part) = submit_part {
+        args.push("--submit".to_string());
+        args.push(submit_part.to_string());
+    }
+
+    spawn(&args);
+}
+
+/// Arguments to run the solution of `day`, up to and including the `--` separator.
+fn cargo_args(day: Day, release: bool, dhat: bool, allocs: bool) -> Vec<String> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,19 +101,40 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
-    cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if allocs {
+        cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
     }
 
+    cmd_args.push("--".to_string());
+    cmd_args
+}
+
+/// Run cargo with inherited stdio and return whether it succeeded. Failures are printed by cargo itself.
+fn spawn(args: &[impl AsRef<std::ffi::OsStr>]) -> bool {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap().success()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ANSI_CLEAR_SCREEN, clear_screen};
+    use std::io::BufWriter;
+
+    #[test]
+    fn clears_screen_before_output_of_runs() {
+        let mut out = BufWriter::new(vec![]);
+        clear_screen(&mut out).unwrap();
+
+        // a child process writes to the underlying stdout, so nothing may be left in the buffer.
+        assert!(out.buffer().is_empty());
+        assert_eq!(out.get_ref().as_slice(), ANSI_CLEAR_SCREEN.as_bytes());
+    }
 }
@@ -1,15 +1,28 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::history::{self, Entry, History};
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::timings::{Timings, format_nanos};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub use crate::template::run_multi::HeapStats;
+
+/// Slowdown in percent above which `--compare` reports a part as regressed.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<f64>,
+    heap_stats: HeapStats,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || compare.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,12 +34,25 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, heap_stats).unwrap();
+    let entries = history::entries_from_timings(
+        &timings,
+        history::current_commit().as_deref(),
+        history::now(),
+    );
+
+    let regressions = compare.map_or(0, |threshold| {
+        print_comparison(&History::read_from_file(), &entries, threshold)
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = History::append_file(&entries) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
@@ -37,4 +63,50 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Print the change of the median of every part versus the last stored run and return the number of regressions.
+fn print_comparison(history: &History, entries: &[Entry], threshold: f64) -> usize {
+    let comparisons = history.compare(entries);
+
+    println!();
+    if comparisons.is_empty() {
+        println!("No stored benchmarks to compare against.");
+        return 0;
+    }
+
+    println!("{ANSI_BOLD}Compared to last stored run{ANSI_RESET} (threshold: +{threshold}%)");
+
+    let mut regressions = 0;
+
+    for comparison in &comparisons {
+        let regressed = comparison.is_regression(threshold);
+        regressions += usize::from(regressed);
+        let (previous, current) = comparison.compared_nanos();
+
+        println!(
+            "Day {} part {}: {} -> {} ({:+.1}%{}){}",
+            comparison.day,
+            comparison.part,
+            format_nanos(previous),
+            format_nanos(current),
+            comparison.change(),
+            comparison
+                .previous
+                .commit
+                .as_ref()
+                .map_or(String::new(), |commit| format!(" vs {commit}")),
+            if regressed { " REGRESSED" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} part(s) regressed by more than {threshold}%.");
+    }
+
+    regressions
 }
@@ -0,0 +1,167 @@
+/// Example inputs and their expected answers, checked by `cargo solve` before the real input is run.
+///
+/// Every example `data/examples/NN.txt` or `data/examples/NN-2.txt` can have its answers recorded next to it in
+/// `NN.answers` or `NN-2.answers`, with one `part_<n>: <answer>` line per part.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::Day;
+
+/// An example input with at least one recorded answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File name of the input, e.g. `06-2.txt`.
+    pub name: String,
+    pub input: String,
+    /// Expected answers of part 1 and 2, if recorded.
+    pub answers: [Option<String>; 2],
+}
+
+impl Example {
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers.get(usize::from(part).checked_sub(1)?)?.as_deref()
+    }
+}
+
+/// Matches `NN.txt` and numbered examples like `NN-2.txt`.
+pub fn is_example_of(name: &str, day: Day) -> bool {
+    name.strip_suffix(".txt")
+        .and_then(|stem| stem.strip_prefix(&day.to_string()))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+/// Parse the contents of an answers file. Blank lines and lines starting with `#` are ignored.
+pub fn parse_answers(contents: &str) -> Result<[Option<String>; 2], String> {
+    let mut answers = [None, None];
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (slot, answer) = match line.split_once(':') {
+            Some(("part_1", answer)) => (0, answer),
+            Some(("part_2", answer)) => (1, answer),
+            _ => {
+                return Err(format!(
+                    "line {}: expected `part_1: <answer>` or `part_2: <answer>`, found {line:?}.",
+                    index + 1
+                ));
+            }
+        };
+
+        answers[slot] = Some(answer.trim().to_string());
+    }
+
+    Ok(answers)
+}
+
+/// All examples of `day` in `<root>/data/examples` that have an answers file.
+pub fn read_examples(root: &Path, day: Day) -> Result<Vec<Example>, String> {
+    let Ok(entries) = fs::read_dir(root.join("data").join("examples")) else {
+        return Ok(vec![]);
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| is_example_of(name, day))
+        })
+        .collect();
+    // `NN.txt` before `NN-2.txt`, and `NN-2.txt` before `NN-10.txt`.
+    paths.sort_by_key(|path| (path.as_os_str().len(), path.clone()));
+
+    let mut examples = vec![];
+
+    for path in paths {
+        let Ok(answers) = fs::read_to_string(path.with_extension("answers")) else {
+            continue;
+        };
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let answers = parse_answers(&answers).map_err(|e| format!("{name}: {e}"))?;
+        let input = fs::read_to_string(&path).map_err(|e| format!("{name}: {e}"))?;
+
+        examples.push(Example {
+            name,
+            input,
+            answers,
+        });
+    }
+
+    Ok(examples)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_example_of, parse_answers, read_examples};
+    use crate::day;
+    use std::{env, fs};
+
+    #[test]
+    fn matches_examples_of_day() {
+        assert!(is_example_of("03.txt", day!(3)));
+        assert!(is_example_of("03-2.txt", day!(3)));
+        assert!(!is_example_of("03.answers", day!(3)));
+        assert!(!is_example_of("13.txt", day!(3)));
+        assert!(!is_example_of("03.txt", day!(13)));
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse_answers("# from the puzzle\npart_2:  3263827 \n\npart_1: 4277556\n").unwrap();
+        assert_eq!(answers, [Some("4277556".into()), Some("3263827".into())]);
+
+        assert_eq!(parse_answers("part_2: 2").unwrap(), [None, Some("2".into())]);
+        assert_eq!(
+            parse_answers("part_1: 1\npart 2: 2").unwrap_err(),
+            "line 2: expected `part_1: <answer>` or `part_2: <answer>`, found \"part 2: 2\"."
+        );
+    }
+
+    #[test]
+    fn reads_examples_with_answers() {
+        let root = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let examples_dir = root.join("data/examples");
+        fs::create_dir_all(&examples_dir).unwrap();
+
+        for (file, contents) in [
+            ("11.txt", "a"),
+            ("11.answers", "part_1: 5"),
+            ("11-2.txt", "b"),
+            ("11-2.answers", "part_2: 2"),
+            ("11-3.txt", "no answers"),
+            ("12.txt", "c"),
+            ("12.answers", "part_1: 2"),
+        ] {
+            fs::write(examples_dir.join(file), contents).unwrap();
+        }
+
+        let examples = read_examples(&root, day!(11)).unwrap();
+        let summary: Vec<_> = examples
+            .iter()
+            .map(|e| (e.name.as_str(), e.input.as_str(), e.answer(1), e.answer(2)))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("11.txt", "a", Some("5"), None),
+                ("11-2.txt", "b", None, Some("2")),
+            ]
+        );
+
+        fs::write(examples_dir.join("11.answers"), "5").unwrap();
+        assert!(read_examples(&root, day!(11)).unwrap_err().starts_with("11.txt: line 1"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,436 @@
+/// Append-only history of benchmark runs, used to detect regressions between runs.
+/// Every stored part is one JSON object per line in `data/timings-history.jsonl`.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::timings::Timings;
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// The benchmark result of a single part at a point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub day: Day,
+    pub part: u8,
+    /// Output of `git describe --always --dirty` when the run was made, if available.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Average duration of the part.
+    pub nanos: f64,
+    /// Median duration of the benchmark runs. `None` for entries stored before medians were recorded.
+    pub median_nanos: Option<f64>,
+}
+
+/// Represents all stored benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<Entry>,
+}
+
+/// A part of the current run next to its last stored timing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub previous: Entry,
+    pub current: Entry,
+}
+
+impl Comparison {
+    /// The previous and current duration that are compared: the medians if both runs recorded one, else the averages.
+    pub fn compared_nanos(&self) -> (f64, f64) {
+        match (self.previous.median_nanos, self.current.median_nanos) {
+            (Some(previous), Some(current)) => (previous, current),
+            _ => (self.previous.nanos, self.current.nanos),
+        }
+    }
+
+    /// Relative change versus the previous run, in percent. Positive values are slower.
+    pub fn change(&self) -> f64 {
+        let (previous, current) = self.compared_nanos();
+        (current / previous - 1.0) * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+impl History {
+    /// Rehydrate the history from its file. If not present, returns an empty history.
+    /// Malformed lines are skipped with a warning, so that one bad line does not hide all other runs.
+    pub fn read_from_file() -> Self {
+        let Ok(contents) = fs::read_to_string(HISTORY_FILE_PATH) else {
+            return Self::default();
+        };
+
+        let (history, errors) = Self::parse(&contents);
+        for error in errors {
+            eprintln!("Skipping entry of {HISTORY_FILE_PATH}: {error}");
+        }
+        history
+    }
+
+    /// Parse the lines of a history file, returning the valid entries and an error for each malformed line.
+    pub fn parse(contents: &str) -> (Self, Vec<String>) {
+        let mut entries = vec![];
+        let mut errors = vec![];
+
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry = JsonValue::from_str(line)
+                .or(Err("not a valid JSON line.".to_string()))
+                .and_then(|json| Entry::try_from(&json));
+
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(e) => errors.push(format!("line {}: {e}", index + 1)),
+            }
+        }
+
+        (History { entries }, errors)
+    }
+
+    /// Append entries to the history file, creating it if necessary.
+    pub fn append_file(entries: &[Entry]) -> Result<(), Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+
+        for entry in entries {
+            let line = JsonValue::from(entry)
+                .stringify()
+                .map_err(|e| Error::other(e.to_string()))?;
+            writeln!(file, "{line}")?;
+        }
+
+        Ok(())
+    }
+
+    /// The most recently stored entry for a part.
+    pub fn latest(&self, day: Day, part: u8) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .filter(|e| e.day == day && e.part == part)
+            .max_by_key(|e| e.timestamp)
+    }
+
+    /// The commit of the most recently stored run, if it was recorded.
+    pub fn latest_commit(&self) -> Option<&str> {
+        self.entries
+            .iter()
+            .max_by_key(|e| e.timestamp)?
+            .commit
+            .as_deref()
+    }
+
+    /// All stored entries for a part, oldest first.
+    pub fn series(&self, day: Day, part: u8) -> Vec<&Entry> {
+        let mut series: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|e| e.day == day && e.part == part)
+            .collect();
+        series.sort_by_key(|e| e.timestamp);
+        series
+    }
+
+    /// Pair every entry of `current` with the latest stored entry of the same part. Parts without history are skipped.
+    pub fn compare(&self, current: &[Entry]) -> Vec<Comparison> {
+        current
+            .iter()
+            .filter_map(|entry| {
+                let previous = self.latest(entry.day, entry.part)?;
+                Some(Comparison {
+                    day: entry.day,
+                    part: entry.part,
+                    previous: previous.clone(),
+                    current: entry.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Convert the timings of a run into history entries, one per timed part.
+pub fn entries_from_timings(timings: &Timings, commit: Option<&str>, timestamp: u64) -> Vec<Entry> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            timing.parts().map(move |(part, part_timing)| Entry {
+                day: timing.day,
+                part,
+                commit: commit.map(String::from),
+                timestamp,
+                nanos: part_timing.nanos,
+                median_nanos: part_timing.median_nanos,
+            })
+        })
+        .collect()
+}
+
+/// The checked out commit, or `None` outside of a git repository.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("timestamp".into(), JsonValue::Number(value.timestamp as f64));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert(
+            "median_nanos".into(),
+            value.median_nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>().copied());
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let part = number("part")
+            .filter(|part| *part == 1.0 || *part == 2.0)
+            .ok_or("Expected entry.part to be 1 or 2.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let timestamp = number("timestamp").ok_or("Expected entry.timestamp to be a number.")?;
+        let nanos = number("nanos").ok_or("Expected entry.nanos to be a number.")?;
+
+        // entries stored before medians were recorded lack the key.
+        let median_nanos = match json.get("median_nanos") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .copied()
+                    .ok_or("Expected entry.median_nanos to be null or a number.")?,
+            ),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (part, timestamp) = (part as u8, timestamp as u64);
+
+        Ok(Entry {
+            day,
+            part,
+            commit: commit.cloned(),
+            timestamp,
+            nanos,
+            median_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Entry, History, entries_from_timings};
+    use crate::day;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+
+    fn entry(day: u8, part: u8, timestamp: u64, nanos: f64) -> Entry {
+        Entry {
+            day: crate::template::Day::new(day).unwrap(),
+            part,
+            commit: Some("abc1234".into()),
+            timestamp,
+            nanos,
+            median_nanos: Some(nanos),
+        }
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let mut unversioned = entry(3, 2, 1_700_000_000, 74.13);
+        unversioned.commit = None;
+
+        let lines = [entry(1, 1, 1_700_000_000, 1500.0), unversioned]
+            .iter()
+            .map(|e| tinyjson::JsonValue::from(e).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let (history, errors) = History::parse(&(lines + "\n"));
+        assert!(errors.is_empty());
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[0], entry(1, 1, 1_700_000_000, 1500.0));
+        assert_eq!(history.entries[1].commit, None);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let valid = tinyjson::JsonValue::from(&entry(1, 1, 10, 100.0)).stringify().unwrap();
+        let contents = format!(
+            "{valid}\n{{ \"day\": \"01\", \"part\": 3, \"commit\": null, \"timestamp\": 0, \"nanos\": 1 }}\nnot json\n\n{valid}\n"
+        );
+
+        let (history, errors) = History::parse(&contents);
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(
+            errors,
+            ["line 2: Expected entry.part to be 1 or 2.", "line 3: not a valid JSON line."]
+        );
+    }
+
+    #[test]
+    fn finds_latest_entry() {
+        let history = History {
+            entries: vec![
+                entry(1, 1, 20, 100.0),
+                entry(1, 1, 30, 300.0),
+                entry(1, 2, 40, 400.0),
+                entry(1, 1, 10, 200.0),
+            ],
+        };
+
+        assert_eq!(history.latest(day!(1), 1).map(|e| e.nanos), Some(300.0));
+        assert_eq!(history.latest(day!(2), 1), None);
+        assert_eq!(history.latest_commit(), Some("abc1234"));
+        assert_eq!(History::default().latest_commit(), None);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let history = History {
+            entries: vec![entry(1, 1, 10, 100.0), entry(1, 2, 10, 100.0)],
+        };
+
+        let current = [entry(1, 1, 20, 125.0), entry(1, 2, 20, 105.0), entry(2, 1, 20, 50.0)];
+        let comparisons = history.compare(&current);
+
+        assert_eq!(comparisons.len(), 2);
+        assert!((comparisons[0].change() - 25.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[1].is_regression(10.0));
+        assert!(comparisons[1].is_regression(2.5));
+    }
+
+    #[test]
+    fn compares_medians() {
+        let history = History {
+            entries: vec![entry(1, 1, 10, 100.0)],
+        };
+
+        // a few slow samples raise the average, but not the median.
+        let mut outliers = entry(1, 1, 20, 150.0);
+        outliers.median_nanos = Some(101.0);
+
+        let comparisons = history.compare(&[outliers]);
+        assert_eq!(comparisons[0].compared_nanos(), (100.0, 101.0));
+        assert!(!comparisons[0].is_regression(10.0));
+
+        // the median got slower, the average did not.
+        let mut slower = entry(1, 1, 20, 100.0);
+        slower.median_nanos = Some(120.0);
+        assert!(history.compare(&[slower])[0].is_regression(10.0));
+    }
+
+    #[test]
+    fn falls_back_to_averages() {
+        let mut stored = entry(1, 1, 10, 100.0);
+        stored.median_nanos = None;
+        let history = History {
+            entries: vec![stored],
+        };
+
+        let mut current = entry(1, 1, 20, 150.0);
+        current.median_nanos = Some(101.0);
+
+        let comparisons = history.compare(&[current]);
+        assert_eq!(comparisons[0].compared_nanos(), (100.0, 150.0));
+        assert!(comparisons[0].is_regression(10.0));
+
+        let (history, errors) = History::parse(
+            "{ \"day\": \"01\", \"part\": 1, \"commit\": null, \"timestamp\": 0, \"nanos\": 1 }\n",
+        );
+        assert!(errors.is_empty());
+        assert_eq!(history.entries[0].median_nanos, None);
+    }
+
+    #[test]
+    fn converts_timings() {
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(4),
+                part_1: Some(PartTiming {
+                    nanos: 1500.0,
+                    median_nanos: Some(1400.0),
+                    samples: Some(10),
+                    answer_hash: None,
+                    memory: None,
+                }),
+                part_2: None,
+                total_nanos: 1500.0,
+            }],
+        };
+
+        let entries = entries_from_timings(&timings, None, 5);
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].part, entries[0].nanos, entries[0].timestamp), (1, 1500.0, 5));
+        assert_eq!(entries[0].median_nanos, Some(1400.0));
+    }
+}
@@ -1,5 +1,6 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
@@ -7,9 +8,14 @@ pub mod runner;
 pub use day::*;
 
 mod day;
+mod examples;
+mod history;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -59,8 +65,19 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(feature = "count-allocs")]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
+
+            if std::env::args().any(|x| x == "--examples") {
+                let mut passed = true;
+                $( passed &= check_examples($func, DAY, $part); )*
+                std::process::exit(if passed { 0 } else { 1 });
+            }
+
             let input = $crate::template::read_file("inputs", DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
@@ -0,0 +1,253 @@
+/// Reads the puzzle description that `aoc-cli` saves as Markdown to `data/puzzles/NN.md`.
+use std::fs;
+
+use crate::template::{Day, aoc_cli};
+
+/// The downloaded description of `day`, if any.
+pub fn read_puzzle(day: Day) -> Option<String> {
+    fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()
+}
+
+/// An expected answer, and the example it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    /// Index into [`PuzzleExamples::inputs`].
+    pub example: usize,
+    pub value: String,
+}
+
+/// Example inputs found in the description, in order of appearance, and the expected answer of each part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub inputs: Vec<String>,
+    pub answers: [Option<Answer>; 2],
+}
+
+enum Block {
+    Text(String),
+    Code(String),
+}
+
+/// The heading of the puzzle, e.g. `Day 1: Secret Entrance`.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let heading = line.replace('\\', "");
+        let title = heading.split_once("--- ")?.1.strip_suffix(" ---")?.trim();
+        title.starts_with("Day ").then(|| title.to_string())
+    })
+}
+
+/// Find the examples of both parts. An example is a code block right after a paragraph containing "For example" or
+/// ending in "example:". The answer of a part is the last emphasised code span, e.g. `` `*11*` ``, of the last
+/// paragraph that has one, and belongs to the example introduced most recently before that paragraph.
+pub fn examples(markdown: &str) -> PuzzleExamples {
+    let mut examples = PuzzleExamples::default();
+    // part two often continues with the last example of part one.
+    let mut current_example = None;
+
+    for (part, section) in part_sections(markdown).into_iter().enumerate() {
+        let blocks = blocks(section);
+
+        for (index, block) in blocks.iter().enumerate() {
+            match block {
+                Block::Code(code) => {
+                    let follows_introduction = index.checked_sub(1).is_some_and(
+                        |previous| matches!(&blocks[previous], Block::Text(text) if introduces_example(text)),
+                    );
+
+                    if follows_introduction {
+                        let position = examples
+                            .inputs
+                            .iter()
+                            .position(|input| input == code)
+                            .unwrap_or_else(|| {
+                                examples.inputs.push(code.clone());
+                                examples.inputs.len() - 1
+                            });
+                        current_example = Some(position);
+                    }
+                }
+                Block::Text(text) => {
+                    if let (Some(example), Some(value)) =
+                        (current_example, emphasised_code_spans(text).pop())
+                    {
+                        examples.answers[part] = Some(Answer { example, value });
+                    }
+                }
+            }
+        }
+    }
+
+    examples
+}
+
+/// The text of part one and, once unlocked, part two.
+fn part_sections(markdown: &str) -> Vec<&str> {
+    const PART_TWO: &str = "--- Part Two ---";
+
+    match markdown.find(PART_TWO) {
+        Some(start) => vec![&markdown[..start], &markdown[start + PART_TWO.len()..]],
+        None => vec![markdown],
+    }
+}
+
+/// Split Markdown into paragraphs and fenced code blocks.
+fn blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut current = String::new();
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            let text = std::mem::take(&mut current);
+            if in_code {
+                blocks.push(Block::Code(text.trim_end_matches('\n').to_string() + "\n"));
+            } else if !text.trim().is_empty() {
+                blocks.push(Block::Text(text));
+            }
+            in_code = !in_code;
+        } else if !in_code && line.trim().is_empty() {
+            let text = std::mem::take(&mut current);
+            if !text.trim().is_empty() {
+                blocks.push(Block::Text(text));
+            }
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+
+    if !in_code && !current.trim().is_empty() {
+        blocks.push(Block::Text(current));
+    }
+
+    blocks
+}
+
+fn introduces_example(text: &str) -> bool {
+    let text = text.trim().to_lowercase();
+    text.contains("for example") || text.ends_with("example:")
+}
+
+/// Code spans that are emphasised either inside (`` `*42*` ``) or outside (``*`42`*``).
+fn emphasised_code_spans(text: &str) -> Vec<String> {
+    let pieces: Vec<&str> = text.split('`').collect();
+
+    (1..pieces.len().saturating_sub(1))
+        .step_by(2)
+        .filter_map(|index| {
+            let code = pieces[index];
+            let inner = code.trim_matches('*');
+
+            let emphasised_inside =
+                code.len() > inner.len() && code.starts_with('*') && code.ends_with('*');
+            let emphasised_outside =
+                pieces[index - 1].ends_with('*') && pieces[index + 1].starts_with('*');
+
+            (!inner.is_empty() && (emphasised_inside || emphasised_outside))
+                .then(|| inner.to_string())
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, emphasised_code_spans, examples, title};
+
+    #[test]
+    fn finds_title() {
+        let markdown =
+            "\\--- Day 5: Cafeteria ---\n----------\n\nAs the forklifts break through the wall...";
+        assert_eq!(title(markdown).as_deref(), Some("Day 5: Cafeteria"));
+
+        let heading = "## \\--- Day 12: Christmas Tree Farm ---\n";
+        assert_eq!(
+            title(heading).as_deref(),
+            Some("Day 12: Christmas Tree Farm")
+        );
+
+        assert_eq!(
+            title("Your puzzle answer was `42`.\n--- Part Two ---"),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_emphasised_code_spans() {
+        let text = "in total `*11*`, or *`12`* and **`13`**, but not `14` nor `*` alone.";
+        assert_eq!(emphasised_code_spans(text), ["11", "12", "13"]);
+    }
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let examples = examples(include_str!("fixtures/puzzle.md"));
+
+        assert_eq!(
+            examples.inputs,
+            ["3 1 2\n4 4\n7\n", "3 1 2 | r\n4 4 | g\n7 | r\n  2 | b\n"]
+        );
+        assert_eq!(
+            examples.answers,
+            [
+                Some(Answer {
+                    example: 0,
+                    value: "2".into()
+                }),
+                Some(Answer {
+                    example: 1,
+                    value: "10".into()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn assigns_answers_to_the_preceding_example() {
+        let markdown = "For example:\n\n```\n1\n```\n\nThis gives `*5*`.\n\nAnother example:\n\n```\n2\n```\n\nThis gives not `*8*` but `*7*`.\n\nMore text without an answer.";
+        assert_eq!(
+            examples(markdown).answers[0],
+            Some(Answer {
+                example: 1,
+                value: "7".into()
+            })
+        );
+
+        let markdown = "For example:\n\n```\n1\n```\n\nThis gives `*5*`.\n\nAnother example:\n\n```\n2\n```\n\nThis one is left to you.";
+        assert_eq!(
+            examples(markdown).answers[0],
+            Some(Answer {
+                example: 0,
+                value: "5".into()
+            })
+        );
+    }
+
+    #[test]
+    fn extracts_reused_examples_before_part_two() {
+        let markdown = "Here is an example:\n\n```\n1\n2\n```\n\nThe sum is `*3*`.\n\n## \\--- Part Two ---\n\nNow in the same example, the product is `*2*`.";
+        let examples = examples(markdown);
+
+        assert_eq!(examples.inputs, ["1\n2\n"]);
+        assert_eq!(
+            examples.answers[1],
+            Some(Answer {
+                example: 0,
+                value: "2".into()
+            })
+        );
+
+        let (part_one, _) = markdown.split_once("## ").unwrap();
+        assert_eq!(
+            super::examples(part_one).answers,
+            [
+                Some(Answer {
+                    example: 0,
+                    value: "3".into()
+                }),
+                None
+            ]
+        );
+    }
+}
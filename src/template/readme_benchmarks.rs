@@ -1,11 +1,15 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, str::FromStr};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::timings::{PartTiming, Timing, Timings, format_bytes, format_nanos};
+use crate::template::{Day, aoc_cli};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static SUMMARY_MARKER: &str = "<!--- benchmarking summary --->";
+
+/// Width of the bar chart column in characters.
+const CHART_WIDTH: usize = 16;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,13 +29,176 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
+/// A column of the benchmark table, configured via `AOC_README_COLUMNS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Average time of part 1.
+    Part1,
+    /// Average time of part 2.
+    Part2,
+    /// Whether each part was solved, i.e. produced an answer when it was timed.
+    Solved,
+    /// Number of benchmark samples per part.
+    Samples,
+    /// Peak heap usage per part, when profiled with dhat.
+    Memory,
+    /// Share of the day in the total time.
+    Share,
+    /// Bar chart of the day's time relative to the slowest day.
+    Chart,
+}
+
+/// Row order of the benchmark table, configured via `AOC_README_SORT`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Time,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeOptions {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    /// Shown in the heading of the summary section.
+    pub year: Option<u16>,
+}
+
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "solved" => Ok(Column::Solved),
+            "samples" => Ok(Column::Samples),
+            "memory" => Ok(Column::Memory),
+            "share" => Ok(Column::Share),
+            "chart" => Ok(Column::Chart),
+            x => Err(Error::Parser(format!("unknown benchmark column `{x}`."))),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(SortOrder::Day),
+            "time" => Ok(SortOrder::Time),
+            x => Err(Error::Parser(format!("unknown benchmark sort order `{x}`."))),
+        }
+    }
+}
+
+impl Default for ReadmeOptions {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Part1, Column::Part2],
+            sort: SortOrder::Day,
+            year: None,
+        }
+    }
+}
+
+impl ReadmeOptions {
+    /// Read options from `AOC_README_COLUMNS` (comma-separated) and `AOC_README_SORT`, falling back to the defaults.
+    pub fn from_env() -> Result<Self, Error> {
+        let mut options = Self {
+            year: aoc_cli::get_year(),
+            ..Self::default()
+        };
+
+        if let Ok(columns) = env::var("AOC_README_COLUMNS") {
+            options.columns = columns
+                .split(',')
+                .filter(|c| !c.trim().is_empty())
+                .map(Column::from_str)
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Ok(sort) = env::var("AOC_README_SORT") {
+            options.sort = sort.parse()?;
+        }
+
+        Ok(options)
+    }
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Solved => "Solved",
+            Column::Samples => "Samples",
+            Column::Memory => "Memory",
+            Column::Share => "Share",
+            Column::Chart => "Chart",
+        }
+    }
+
+    fn alignment(self) -> &'static str {
+        match self {
+            Column::Chart => ":---",
+            _ => ":---:",
+        }
+    }
+
+    fn cell(self, timing: &Timing, total_nanos: f64, max_nanos: f64) -> String {
+        match self {
+            Column::Part1 => format_part(timing.part_1.as_ref()),
+            Column::Part2 => format_part(timing.part_2.as_ref()),
+            Column::Solved => [&timing.part_1, &timing.part_2]
+                .iter()
+                .map(|p| if p.is_some() { "✔" } else { "✖" })
+                .collect::<Vec<_>>()
+                .join(" "),
+            Column::Samples => timing.per_part(|p| p.samples.map(|x| x.to_string())),
+            Column::Memory => timing.per_part(|p| p.memory.map(|m| format_bytes(m.peak_bytes))),
+            Column::Share if total_nanos > 0.0 => {
+                format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)
+            }
+            Column::Share => "-".into(),
+            Column::Chart => bar(timing.total_nanos, max_nanos, CHART_WIDTH),
+        }
+    }
+}
+
+fn format_part(part: Option<&PartTiming>) -> String {
+    format!("`{}`", part.map_or_else(|| "-".into(), |p| format_nanos(p.nanos)))
+}
+
+/// A bar of up to `width` characters, in eighths of a character.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn bar(value: f64, max: f64, width: usize) -> String {
+    const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    if max <= 0.0 || value <= 0.0 {
+        return String::new();
+    }
+
+    // always show a sliver for non-zero values.
+    let eighths = ((value / max * (width * 8) as f64).round() as usize).max(1);
+
+    let mut bar = "█".repeat(eighths / 8);
+    let rest = eighths % 8;
+    if rest > 0 {
+        bar.push(EIGHTHS[rest]);
+    }
+    bar
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,25 +219,42 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn construct_table(prefix: &str, timings: Timings, total_millis: f64, options: &ReadmeOptions) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let columns = &options.columns;
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!(
+            "| Day |{}",
+            columns.iter().map(|c| format!(" {} |", c.header())).collect::<String>()
+        ),
+        format!(
+            "| :---: |{}",
+            columns.iter().map(|c| format!(" {} |", c.alignment())).collect::<String>()
+        ),
     ];
 
-    for timing in timings.data {
+    let mut data = timings.data;
+    if options.sort == SortOrder::Time {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos).then(a.day.cmp(&b.day)));
+    }
+
+    let total_nanos: f64 = data.iter().map(|t| t.total_nanos).sum();
+    let max_nanos = data.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+
+    for timing in &data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            columns
+                .iter()
+                .map(|c| format!(" {} |", c.cell(timing, total_nanos, max_nanos)))
+                .collect::<String>()
         ));
     }
 
@@ -81,46 +265,107 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn construct_summary(prefix: &str, timings: &Timings, total_millis: f64, year: Option<u16>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Summary"),
+        None => format!("{prefix} Summary"),
+    };
+
+    let parts: usize = timings.data.iter().map(|t| t.parts().count()).sum();
+    let by_time = |a: &&Timing, b: &&Timing| a.total_nanos.total_cmp(&b.total_nanos);
+    let link = |timing: Option<&Timing>| {
+        timing.map_or_else(
+            || "-".into(),
+            |t| {
+                format!(
+                    "[Day {}]({}) `{}`",
+                    t.day.into_inner(),
+                    get_path_for_bin(t.day),
+                    format_nanos(t.total_nanos)
+                )
+            },
+        )
+    };
+
+    [
+        SUMMARY_MARKER.into(),
+        header,
+        String::new(),
+        "| Days | Parts | Total | Fastest day | Slowest day |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
+        format!(
+            "| {} | {} | `{:.2}ms` | {} | {} |",
+            timings.data.len(),
+            parts,
+            total_millis,
+            link(timings.data.iter().min_by(by_time)),
+            link(timings.data.iter().max_by(by_time)),
+        ),
+        SUMMARY_MARKER.into(),
+    ]
+    .join("\n")
+}
+
+fn update_content(s: &mut String, timings: Timings, total_millis: f64, options: &ReadmeOptions) -> Result<(), Error> {
+    // the summary section is optional, only update it if the readme has its markers.
+    if s.contains(SUMMARY_MARKER) {
+        let positions = locate_table(s, SUMMARY_MARKER)?;
+        let summary = construct_summary("##", &timings, total_millis, options.year);
+        s.replace_range(positions.pos_start..positions.pos_end, &summary);
+    }
+
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
+    let options = ReadmeOptions::from_env()?;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, &options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
+    use super::{Column, MARKER, ReadmeOptions, SUMMARY_MARKER, SortOrder, bar, format_bytes, update_content};
     use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::template::runner::MemoryStats;
+    use crate::template::timings::PartTiming;
+
+    fn part(nanos: f64) -> PartTiming {
+        PartTiming {
+            nanos,
+            median_nanos: Some(nanos),
+            samples: Some(10),
+            answer_hash: None,
+            memory: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(part(10e6)),
+                    part_2: Some(part(20e6)),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(part(30e6)),
+                    part_2: Some(part(40e6)),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: Some(part(40e6)),
+                    part_2: Some(part(50e6)),
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +376,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &ReadmeOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &ReadmeOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &ReadmeOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &ReadmeOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &ReadmeOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +405,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &ReadmeOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +413,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +425,81 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_configured_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2 = None;
+        timings.data[1].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+            allocations: 3,
+            total_bytes: 4096,
+            peak_bytes: 1536,
+        });
+
+        let options = ReadmeOptions {
+            columns: vec![Column::Solved, Column::Samples, Column::Memory, Column::Share, Column::Chart],
+            sort: SortOrder::Time,
+            year: None,
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+        let rows: Vec<&str> = s.lines().skip(3).take(5).collect();
+
+        assert_eq!(
+            rows,
+            [
+                "| Day | Solved | Samples | Memory | Share | Chart |",
+                "| :---: | :---: | :---: | :---: | :---: | :--- |",
+                "| [Day 4](./src/bin/04.rs) | ✔ ✔ | 10 / 10 | - / - | 47.4% | ████████████████ |",
+                "| [Day 2](./src/bin/02.rs) | ✔ ✔ | 10 / 10 | 1.5 KiB / - | 36.8% | ████████████▌ |",
+                "| [Day 1](./src/bin/01.rs) | ✔ ✖ | 10 / - | - / - | 15.8% | █████▍ |",
+            ]
+        );
+    }
+
+    #[test]
+    fn updates_summary_if_present() {
+        let options = ReadmeOptions {
+            year: Some(2025),
+            ..ReadmeOptions::default()
+        };
+
+        let mut s = format!("{MARKER}{MARKER}\n{SUMMARY_MARKER}\nold\n{SUMMARY_MARKER}\n");
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &options).unwrap();
+
+        let summary = &s[s.find(SUMMARY_MARKER).unwrap()..];
+        let expected = [
+            SUMMARY_MARKER,
+            "## 2025 Summary",
+            "",
+            "| Days | Parts | Total | Fastest day | Slowest day |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| 3 | 6 | `190.00ms` | [Day 1](./src/bin/01.rs) `30.0s` | [Day 4](./src/bin/04.rs) `90.0s` |",
+            SUMMARY_MARKER,
+            "",
+        ]
+        .join("\n");
+        assert_eq!(summary, expected);
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!("chart".parse::<Column>().unwrap(), Column::Chart);
+        assert_eq!(" time".parse::<SortOrder>().unwrap(), SortOrder::Time);
+        assert!("median".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn formats_bars_and_bytes() {
+        assert_eq!(bar(1.0, 1.0, 2), "██");
+        assert_eq!(bar(1.0, 4.0, 2), "▌");
+        assert_eq!(bar(1e-9, 1.0, 2), "▏");
+        assert_eq!(bar(0.0, 1.0, 2), "");
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
 }
@@ -0,0 +1,406 @@
+/// Renders stored timings into standalone reports: an HTML page with an inline SVG chart per day,
+/// and a Markdown variant for pasting into PR comments. Neither needs network access to view.
+use std::fmt::Write;
+
+use crate::template::history::{Entry, History};
+use crate::template::timings::{PartTiming, Timing, Timings, format_bytes, format_nanos};
+
+/// Number of most recent history entries shown per part.
+const TREND_LENGTH: usize = 12;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+const PART_COLORS: [&str; 2] = ["#3b82f6", "#f59e0b"];
+
+/// Information about the report itself, shown in its header.
+#[derive(Clone, Debug, Default)]
+pub struct ReportMeta {
+    pub year: Option<u16>,
+    /// Commit of the most recently stored timings, see [`History::latest_commit`].
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub generated_at: u64,
+}
+
+impl ReportMeta {
+    fn title(&self) -> String {
+        match self.year {
+            Some(year) => format!("Advent of Code {year} benchmarks"),
+            None => "Advent of Code benchmarks".into(),
+        }
+    }
+
+    fn subtitle(&self) -> String {
+        let generated = format!("Generated {}", format_timestamp(self.generated_at));
+        match &self.commit {
+            Some(commit) => format!("{generated}, latest timings from {commit}"),
+            None => generated,
+        }
+    }
+}
+
+/// Render a self-contained HTML page.
+pub fn render_html(timings: &Timings, history: &History, meta: &ReportMeta) -> String {
+    let max_nanos = timings
+        .data
+        .iter()
+        .flat_map(|t| t.parts().map(|(_, p)| p.nanos))
+        .fold(1.0, f64::max);
+
+    let mut html = String::new();
+    let title = escape(&meta.title());
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"meta\">{}</p>\n",
+        escape(&meta.subtitle())
+    );
+
+    let _ = writeln!(
+        html,
+        "<table>\n<thead><tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Total</th><th>Samples</th><th>Memory</th><th>Chart</th></tr></thead>\n<tbody>"
+    );
+
+    for timing in &timings.data {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            timing.day,
+            part_cell(timing.part_1.as_ref()),
+            part_cell(timing.part_2.as_ref()),
+            format_nanos(timing.total_nanos),
+            timing.per_part(|p| p.samples.map(|x| x.to_string())),
+            timing.per_part(|p| p.memory.map(|m| format_bytes(m.peak_bytes))),
+            day_chart(timing, history, max_nanos),
+        );
+    }
+
+    let _ = write!(
+        html,
+        "</tbody>\n</table>\n<p class=\"total\">Total: {:.2}ms</p>\n</body>\n</html>\n",
+        timings.total_millis()
+    );
+
+    html
+}
+
+/// Render a Markdown summary. History trends are shown as sparklines of the most recent runs.
+pub fn render_markdown(timings: &Timings, history: &History, meta: &ReportMeta) -> String {
+    let with_trend = !history.entries.is_empty();
+
+    let mut lines = vec![
+        format!("## {}", meta.title()),
+        String::new(),
+        format!("_{}._", meta.subtitle()),
+        String::new(),
+    ];
+
+    if with_trend {
+        lines.push("| Day | Part 1 | Part 2 | Total | Part 1 trend | Part 2 trend |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :--- | :--- |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 | Total |".into());
+        lines.push("| :---: | :---: | :---: | :---: |".into());
+    }
+
+    for timing in &timings.data {
+        let mut row = format!(
+            "| {} | `{}` | `{}` | `{}` |",
+            timing.day,
+            part_cell(timing.part_1.as_ref()),
+            part_cell(timing.part_2.as_ref()),
+            format_nanos(timing.total_nanos)
+        );
+
+        if with_trend {
+            for part in [1, 2] {
+                let _ = write!(row, " {} |", trend(&history.series(timing.day, part)));
+            }
+        }
+
+        lines.push(row);
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+fn part_cell(part: Option<&PartTiming>) -> String {
+    part.map_or_else(|| "-".into(), |p| format_nanos(p.nanos))
+}
+
+/// A sparkline of the most recent entries followed by the change of the last run, e.g. `▁▃█ +12.5%`.
+fn trend(series: &[&Entry]) -> String {
+    let recent = &series[series.len().saturating_sub(TREND_LENGTH)..];
+
+    let (min, max) = recent
+        .iter()
+        .fold((f64::INFINITY, 0.0_f64), |(min, max), e| (min.min(e.nanos), max.max(e.nanos)));
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    let sparkline: String = recent
+        .iter()
+        .map(|e| {
+            if max > min {
+                SPARKS[((e.nanos - min) / (max - min) * (SPARKS.len() - 1) as f64).round() as usize]
+            } else {
+                SPARKS[0]
+            }
+        })
+        .collect();
+
+    match recent {
+        [] => "-".into(),
+        [.., previous, last] => format!(
+            "{sparkline} {:+.1}%",
+            (last.nanos / previous.nanos - 1.0) * 100.0
+        ),
+        [_] => sparkline,
+    }
+}
+
+/// Inline SVG with a bar per part on a log scale and, if there are at least two stored runs, their history.
+fn day_chart(timing: &Timing, history: &History, max_nanos: f64) -> String {
+    const WIDTH: f64 = 320.0;
+    const LABEL: f64 = 48.0;
+    const BAR: f64 = 14.0;
+    const TREND: f64 = 40.0;
+
+    let series: Vec<(u8, Vec<&Entry>)> = [1, 2]
+        .into_iter()
+        .map(|part| (part, history.series(timing.day, part)))
+        .filter(|(_, s)| s.len() >= 2)
+        .collect();
+
+    let bars_height = 2.0 * (BAR + 4.0);
+    let height = if series.is_empty() { bars_height } else { bars_height + TREND + 4.0 };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" viewBox=\"0 0 {WIDTH} {height}\" role=\"img\" aria-label=\"Day {} timings\">",
+        timing.day
+    );
+
+    // log scale so microsecond parts stay visible next to multi-second ones.
+    let scale = (WIDTH - LABEL - 64.0) / max_nanos.max(10.0).log10();
+
+    for (index, (part, part_timing)) in [(1, &timing.part_1), (2, &timing.part_2)].into_iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = index as f64 * (BAR + 4.0);
+        let _ = write!(
+            svg,
+            "<text x=\"0\" y=\"{}\" class=\"label\">Part {part}</text>",
+            y + BAR - 3.0
+        );
+
+        let Some(part_timing) = part_timing else {
+            continue;
+        };
+
+        let width = (part_timing.nanos.max(1.0).log10() * scale).max(1.0);
+        let _ = write!(
+            svg,
+            "<rect x=\"{LABEL}\" y=\"{y}\" width=\"{width:.1}\" height=\"{BAR}\" fill=\"{}\"><title>{}</title></rect><text x=\"{:.1}\" y=\"{}\" class=\"value\">{}</text>",
+            PART_COLORS[index],
+            format_nanos(part_timing.nanos),
+            LABEL + width + 4.0,
+            y + BAR - 3.0,
+            format_nanos(part_timing.nanos),
+        );
+    }
+
+    for (part, entries) in &series {
+        let max = entries.iter().map(|e| e.nanos).fold(1.0, f64::max);
+        #[allow(clippy::cast_precision_loss)]
+        let step = (WIDTH - LABEL) / (entries.len() - 1) as f64;
+
+        #[allow(clippy::cast_precision_loss)]
+        let points: Vec<String> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                format!(
+                    "{:.1},{:.1}",
+                    LABEL + i as f64 * step,
+                    bars_height + 4.0 + TREND * (1.0 - e.nanos / max)
+                )
+            })
+            .collect();
+
+        let _ = write!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"><title>Part {part}: {} runs</title></polyline>",
+            points.join(" "),
+            PART_COLORS[usize::from(*part) - 1],
+            entries.len()
+        );
+    }
+
+    if !series.is_empty() {
+        let _ = write!(
+            svg,
+            "<text x=\"0\" y=\"{}\" class=\"label\">History</text>",
+            bars_height + 4.0 + TREND / 2.0
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM UTC`.
+fn format_timestamp(secs: u64) -> String {
+    let days = secs / 86_400;
+    let (hours, minutes) = ((secs % 86_400) / 3600, (secs % 3600) / 60);
+
+    // civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02} {hours:02}:{minutes:02} UTC")
+}
+
+static STYLE: &str = "body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #1f2937; }
+h1 { font-size: 1.5rem; }
+.meta, .total { color: #6b7280; }
+table { border-collapse: collapse; width: 100%; }
+th, td { padding: 0.35rem 0.6rem; border-bottom: 1px solid #e5e7eb; text-align: center; font-variant-numeric: tabular-nums; }
+td:last-child { text-align: left; }
+svg .label, svg .value { font-size: 11px; fill: #374151; }
+";
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ReportMeta, escape, format_timestamp, render_html, render_markdown, trend};
+    use crate::day;
+    use crate::template::history::{Entry, History};
+    use crate::template::timings::{PartTiming, Timing, Timings};
+
+    fn part(nanos: f64) -> PartTiming {
+        PartTiming {
+            nanos,
+            median_nanos: Some(nanos),
+            samples: Some(10),
+            answer_hash: None,
+            memory: None,
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(part(1500.0)),
+                    part_2: Some(part(2e6)),
+                    total_nanos: 2_001_500.0,
+                },
+                Timing {
+                    day: day!(3),
+                    part_1: Some(part(3e9)),
+                    part_2: None,
+                    total_nanos: 3e9,
+                },
+            ],
+        }
+    }
+
+    fn entry(part: u8, timestamp: u64, nanos: f64) -> Entry {
+        Entry {
+            day: day!(1),
+            part,
+            commit: None,
+            timestamp,
+            nanos,
+            median_nanos: Some(nanos),
+        }
+    }
+
+    fn meta() -> ReportMeta {
+        ReportMeta {
+            year: Some(2025),
+            commit: Some("abc1234".into()),
+            generated_at: 1_733_400_000,
+        }
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_733_400_000), "2024-12-05 12:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+    }
+
+    #[test]
+    fn renders_trends() {
+        let series = [entry(1, 1, 100.0), entry(1, 2, 200.0), entry(1, 3, 150.0)];
+        assert_eq!(trend(&series.iter().collect::<Vec<_>>()), "▁█▅ -25.0%");
+        assert_eq!(trend(&[&series[0]]), "▁");
+        assert_eq!(trend(&[]), "-");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = render_markdown(&get_mock_timings(), &History::default(), &meta());
+        let expected = [
+            "## Advent of Code 2025 benchmarks",
+            "",
+            "_Generated 2024-12-05 12:00 UTC, latest timings from abc1234._",
+            "",
+            "| Day | Part 1 | Part 2 | Total |",
+            "| :---: | :---: | :---: | :---: |",
+            "| 01 | `1.5µs` | `2.0ms` | `2.0ms` |",
+            "| 03 | `3.0s` | `-` | `3.0s` |",
+            "",
+            "**Total: 3002.00ms**",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn renders_markdown_trends() {
+        let history = History {
+            entries: vec![entry(1, 1, 1000.0), entry(1, 2, 1500.0), entry(2, 1, 2e6)],
+        };
+        let markdown = render_markdown(&get_mock_timings(), &history, &meta());
+        assert!(markdown.contains("| 01 | `1.5µs` | `2.0ms` | `2.0ms` | ▁█ +50.0% | ▁ |"));
+        assert!(markdown.contains("| 03 | `3.0s` | `-` | `3.0s` | - | - |"));
+    }
+
+    #[test]
+    fn renders_self_contained_html() {
+        let history = History {
+            entries: vec![entry(1, 1, 1000.0), entry(1, 2, 1500.0)],
+        };
+        let html = render_html(&get_mock_timings(), &history, &meta());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<svg").count(), 2);
+        assert_eq!(html.matches("<polyline").count(), 1);
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src=\"http"));
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(escape("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    }
+}
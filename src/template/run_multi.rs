@@ -7,7 +7,22 @@ use super::{
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// How the heap usage of solutions is recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeapStats {
+    Off,
+    /// With the `count-allocs` feature, during the regular run.
+    Counted,
+    /// With dhat, in a second run, so that profiling does not distort the timings.
+    Profiled,
+}
+
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    heap_stats: HeapStats,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,12 +39,26 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let run_heap_stats = match heap_stats {
+                HeapStats::Counted => HeapStats::Counted,
+                HeapStats::Off | HeapStats::Profiled => HeapStats::Off,
+            };
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, run_heap_stats).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+
+                if heap_stats == HeapStats::Profiled {
+                    println!("{ANSI_ITALIC}Heap profile:{ANSI_RESET}");
+                    let profile =
+                        child_commands::run_solution(day, false, false, HeapStats::Profiled)
+                            .unwrap();
+                    child_commands::parse_memory(&profile, &mut val);
+                }
+
                 timings.push(val);
             }
         });
@@ -67,8 +96,10 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Error, HeapStats, get_path_for_bin};
+    use crate::template::runner::MemoryStats;
+    use crate::template::timings::{PartTiming, hash_answer};
+    use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,8 +107,13 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day. `HeapStats::Profiled` builds with the `dhat` profile instead of `--release`.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        heap_stats: HeapStats,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -86,10 +122,16 @@ pub mod child_commands {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if heap_stats == HeapStats::Profiled {
+            args.extend(["--profile", "dhat", "--features", "dhat-heap"]);
+        } else if is_release {
             args.push("--release");
         }
 
+        if heap_stats == HeapStats::Counted {
+            args.extend(["--features", "count-allocs"]);
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
@@ -143,50 +185,112 @@ pub mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some((nanos, median_nanos, samples)) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                let timing = PartTiming {
+                    nanos,
+                    median_nanos,
+                    samples: Some(samples),
+                    answer_hash: parse_answer(l).map(|answer| hash_answer(&answer)),
+                    memory: MemoryStats::parse(l),
+                };
+                Some((part, timing))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing)| {
+                timings.total_nanos += timing.nanos;
+
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1 = Some(timing);
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(timing);
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
+    /// Attach heap stats printed by a profiled run to the parts of `timing` that were timed.
+    pub fn parse_memory(output: &[String], timing: &mut super::Timing) {
+        for line in output {
+            let Some(memory) = MemoryStats::parse(line) else {
+                continue;
+            };
+
+            let part = line.split(':').next().unwrap_or_default();
+            let part_timing = if part.contains("Part 1") {
+                timing.part_1.as_mut()
+            } else if part.contains("Part 2") {
+                timing.part_2.as_mut()
+            } else {
+                None
+            };
+
+            if let Some(part_timing) = part_timing {
+                part_timing.memory = Some(memory);
+            }
+        }
+    }
+
+    /// The answer printed on a result line, without formatting.
+    /// `None` for multi-line answers, which are printed below the line.
+    fn parse_answer(line: &str) -> Option<String> {
+        // the runner overwrites intermediate output on the same line with `\r`.
+        let line = line.rsplit('\r').next()?;
+        let answer = line
+            .split_once(": ")?
+            .1
+            .rsplit_once(" (")?
+            .0
+            .replace(ANSI_BOLD, "")
+            .replace(ANSI_RESET, "");
+
+        (answer != "▼").then_some(answer)
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
+    /// Parse the average and median duration in nanoseconds and the sample count from a result line.
+    /// The median is only printed for benched parts.
+    fn parse_time(line: &str) -> Option<(f64, Option<f64>, u64)> {
+        let (str_timing, str_samples) = line
             .split(" samples)")
             .next()?
             .split('(')
             .next_back()?
-            .split('@')
-            .next()?
-            .trim();
+            .split_once('@')?;
+
+        let (str_average, str_median) = match str_timing.split_once(", median ") {
+            Some((average, median)) => (average, Some(median)),
+            None => (str_timing, None),
+        };
 
-        let parsed_timing = match str_timing {
+        let median = match str_median {
+            Some(median) => Some(parse_duration(median.trim())?),
+            None => None,
+        };
+
+        Some((
+            parse_duration(str_average.trim())?,
+            median,
+            str_samples.trim().parse().ok()?,
+        ))
+    }
+
+    /// Parse a duration formatted by `Duration`'s `Debug` impl, e.g. `74.13ns`, into nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +309,9 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_memory};
+        use crate::template::runner::MemoryStats;
+        use crate::template::timings::hash_answer;
 
         use crate::day;
 
@@ -220,8 +326,12 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let (part_1, part_2) = (res.part_1.unwrap(), res.part_2.unwrap());
+            assert_approx_eq!(part_1.nanos, 74.13_f64);
+            assert_eq!(part_1.samples, Some(100000));
+            assert_eq!(part_1.answer_hash, Some(hash_answer("0")));
+            assert_approx_eq!(part_2.nanos, 74130000_f64);
+            assert_eq!(part_2.samples, Some(99999));
         }
 
         #[test]
@@ -235,8 +345,73 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            let (part_1, part_2) = (res.part_1.unwrap(), res.part_2.unwrap());
+            assert_approx_eq!(part_1.nanos, 2000000000_f64);
+            assert_eq!(part_1.answer_hash, Some(hash_answer("@ @ @ ( ) ms")));
+            assert_approx_eq!(part_2.nanos, 100000000_f64);
+            assert_eq!(part_2.answer_hash, Some(hash_answer("10s")));
+        }
+
+        #[test]
+        fn parses_formatted_answers() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: \x1b[1m42\x1b[0m > benching\rPart 1: \x1b[1m42\x1b[0m (1.5µs @ 10 samples)".into(),
+                    "Part 2: ▼ (3.0ms @ 10 samples)".into(),
+                    "#..#".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap().answer_hash, Some(hash_answer("42")));
+            assert_eq!(res.part_2.unwrap().answer_hash, None);
+        }
+
+        #[test]
+        fn parses_medians() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (1.5µs, median 1.2µs @ 10 samples)".into(),
+                    "Part 2: 7 (3.0ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            let (part_1, part_2) = (res.part_1.unwrap(), res.part_2.unwrap());
+            assert_approx_eq!(part_1.nanos, 1500_f64);
+            assert_approx_eq!(part_1.median_nanos.unwrap(), 1200_f64);
+            assert_eq!(part_1.samples, Some(10));
+            assert_eq!(part_1.answer_hash, Some(hash_answer("42")));
+            assert_eq!(part_2.median_nanos, None);
+        }
+
+        #[test]
+        fn parses_heap_stats() {
+            let memory = MemoryStats {
+                allocations: 3,
+                total_bytes: 276,
+                peak_bytes: 232,
+            };
+            assert_eq!(MemoryStats::parse(&memory.to_string()), Some(memory));
+
+            let mut res = parse_exec_time(
+                &[
+                    format!("Part 1: 9001 (4.1ms @ 10 samples) {memory}"),
+                    "Part 2: 12 (1.0ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.as_ref().unwrap().memory, Some(memory));
+            assert_eq!(res.part_1.as_ref().unwrap().answer_hash, Some(hash_answer("9001")));
+            assert_eq!(res.part_2.as_ref().unwrap().memory, None);
+
+            parse_memory(
+                &[
+                    "dhat: Total:     276 bytes in 3 blocks".into(),
+                    "Part 2: 12 (1.2ms) [heap: 1 allocs, 64 B total, 64 B peak]".into(),
+                ],
+                &mut res,
+            );
+            assert_eq!(res.part_1.unwrap().memory, Some(memory));
+            assert_eq!(res.part_2.unwrap().memory.map(|m| m.peak_bytes), Some(64));
         }
 
         #[test]
@@ -7,35 +7,120 @@ use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli, examples};
+
+/// Heap usage of a single run of a part, as reported by dhat or the counting allocator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of allocations.
+    pub allocations: u64,
+    /// Bytes allocated in total.
+    pub total_bytes: u64,
+    /// Maximum of bytes live at the same time.
+    pub peak_bytes: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[heap: {} allocs, {} B total, {} B peak]",
+            self.allocations, self.total_bytes, self.peak_bytes
+        )
+    }
+}
+
+impl MemoryStats {
+    /// Find stats printed by the runner in an output line.
+    pub fn parse(line: &str) -> Option<Self> {
+        let stats = line.split("[heap: ").nth(1)?.split(']').next()?;
+        let mut numbers = stats
+            .split(", ")
+            .map(|field| field.split(' ').next()?.parse::<u64>().ok());
+
+        Some(Self {
+            allocations: numbers.next()??,
+            total_bytes: numbers.next()??,
+            peak_bytes: numbers.next()??,
+        })
+    }
+}
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, median, samples, memory) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut stats = format_duration(&duration, &median, samples);
+    if let Some(memory) = memory {
+        stats.push_str(&format!(" {memory}"));
+    }
+
+    print_result(&result, &part_str, &stats);
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run a part against every example with a recorded answer for it. Returns whether all answers matched.
+pub fn check_examples<T: Display>(func: impl Fn(&str) -> Option<T>, day: Day, part: u8) -> bool {
+    let examples = match examples::read_examples(std::path::Path::new("."), day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("Failed to read example answers: {e}");
+            return false;
+        }
+    };
+
+    let mut passed = true;
+
+    for example in &examples {
+        let Some(expected) = example.answer(part) else {
+            continue;
+        };
+
+        let result = func(&example.input).map(|result| result.to_string());
+        let label = format!("Example {} part {part}", example.name);
+
+        match result {
+            Some(result) if result.trim() == expected => {
+                println!("{label}: ✓ {ANSI_BOLD}{expected}{ANSI_RESET}");
+            }
+            result => {
+                passed = false;
+                println!(
+                    "{label}: ✖ {} (expected {ANSI_BOLD}{expected}{ANSI_RESET})",
+                    result.as_deref().unwrap_or("no answer")
+                );
+            }
+        }
+    }
+
+    passed
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Returns the average and the median duration of all executions, next to the number of samples.
+/// With the `dhat-heap` or `count-allocs` feature, the heap usage of the first execution is returned as well.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Duration, u128, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
+        #[cfg(feature = "count-allocs")]
+        crate::template::alloc::reset();
 
-        func(input)
+        let result = func(input);
+        (result, heap_stats())
     };
     let base_time = timer.elapsed();
 
@@ -44,13 +129,37 @@ fn run_timed<I: Copy, T>(
     let run = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (base_time, base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, run.2, memory)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+#[cfg(feature = "dhat-heap")]
+fn heap_stats() -> Option<MemoryStats> {
+    let stats = dhat::HeapStats::get();
+    Some(MemoryStats {
+        allocations: stats.total_blocks,
+        total_bytes: stats.total_bytes,
+        peak_bytes: stats.max_bytes as u64,
+    })
+}
+
+#[cfg(feature = "count-allocs")]
+fn heap_stats() -> Option<MemoryStats> {
+    Some(crate::template::alloc::stats())
+}
+
+#[cfg(not(any(feature = "dhat-heap", feature = "count-allocs")))]
+fn heap_stats() -> Option<MemoryStats> {
+    None
+}
+
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -70,6 +179,8 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
+        #[allow(clippy::cast_possible_truncation)]
+        Duration::from_nanos(median_duration(&mut timers) as u64),
         bench_iterations,
     )
 }
@@ -82,11 +193,23 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// Median of the durations; the mean of both middle values for an even count.
+fn median_duration(numbers: &mut [Duration]) -> u128 {
+    numbers.sort_unstable();
+    let middle = numbers.len() / 2;
+
+    if numbers.len().is_multiple_of(2) {
+        (numbers[middle - 1].as_nanos() + numbers[middle].as_nanos()) / 2
+    } else {
+        numbers[middle].as_nanos()
+    }
+}
+
+fn format_duration(duration: &Duration, median: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?}, median {median:.1?} @ {samples} samples)")
     }
 }
 
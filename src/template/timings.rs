@@ -1,16 +1,40 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::runner::MemoryStats;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the JSON layout written by [`Timings::store_file`].
+/// Version 1 had no `version` key and stored parts as preformatted strings like `"74.13ns"`.
+/// Version 2 stored parts as objects, but without `median_nanos`.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Represents the benchmark of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Average duration of the part.
+    pub nanos: f64,
+    /// Median duration of the benchmark runs, which is what regressions are checked against.
+    /// `None` for parts that were run once and timings migrated from version 1 or 2.
+    pub median_nanos: Option<f64>,
+    /// Number of benchmark runs. `None` for timings migrated from version 1.
+    pub samples: Option<u64>,
+    /// FNV-1a hash of the printed answer, to notice when a change alters the result.
+    /// `None` for multi-line answers and timings migrated from version 1.
+    pub answer_hash: Option<u64>,
+    /// Heap usage, if the part was profiled with dhat.
+    pub memory: Option<MemoryStats>,
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
@@ -21,6 +45,30 @@ pub struct Timings {
     pub data: Vec<Timing>,
 }
 
+impl PartTiming {
+    pub fn duration(&self) -> Duration {
+        nanos_to_duration(self.nanos)
+    }
+}
+
+impl Timing {
+    /// Iterate over the timed parts along with their number.
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &PartTiming)> {
+        [(1, &self.part_1), (2, &self.part_2)]
+            .into_iter()
+            .filter_map(|(part, timing)| Some((part, timing.as_ref()?)))
+    }
+
+    /// Format a value of both parts as `a / b`, with `-` for missing values.
+    pub(crate) fn per_part(&self, value: impl Fn(&PartTiming) -> Option<String>) -> String {
+        [&self.part_1, &self.part_2]
+            .iter()
+            .map(|p| p.as_ref().and_then(&value).unwrap_or_else(|| "-".into()))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
@@ -67,12 +115,52 @@ impl Timings {
     }
 }
 
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Format nanoseconds the same way the runner prints durations, e.g. `74.0ns` or `1.5ms`.
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", nanos_to_duration(nanos))
+}
+
+/// Format a byte count with binary units, e.g. `512 B` or `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and compiler versions.
+pub fn hash_answer(answer: &str) -> u64 {
+    answer.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,9 +176,26 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match json.get("version") {
+            None => 1.0,
+            Some(v) => v
+                .get::<f64>()
+                .copied()
+                .filter(|v| v.fract() == 0.0 && *v >= 1.0)
+                .ok_or("expected `json.version` to be a positive integer.")?,
+        };
+
+        let parse_part: fn(&JsonValue) -> Option<PartTiming> = match version {
+            1.0 => part_from_v1,
+            2.0 | 3.0 => part_from_v2,
+            v => return Err(format!("unsupported timings version {v}.")),
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -99,7 +204,7 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| timing_from_json(timing, parse_part))
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -107,6 +212,51 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert(
+            "median_nanos".into(),
+            value.median_nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "samples".into(),
+            value
+                .samples
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "memory".into(),
+            value.memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+        // hashes are stored as hex strings, JSON numbers cannot represent every u64.
+        map.insert(
+            "answer_hash".into(),
+            value
+                .answer_hash
+                .map_or(JsonValue::Null, |x| JsonValue::String(format!("{x:016x}"))),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("allocations".into(), JsonValue::Number(value.allocations as f64));
+        map.insert("total_bytes".into(), JsonValue::Number(value.total_bytes as f64));
+        map.insert("peak_bytes".into(), JsonValue::Number(value.peak_bytes as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
@@ -114,65 +264,120 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value.part_1.as_ref().map_or(JsonValue::Null, JsonValue::from),
         );
 
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value.part_2.as_ref().map_or(JsonValue::Null, JsonValue::from),
         );
 
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
-    type Error = String;
+/// Version 1 stored the duration as formatted by the runner, e.g. `"74.13ns"`.
+fn part_from_v1(value: &JsonValue) -> Option<PartTiming> {
+    Some(PartTiming {
+        nanos: parse_duration(value.get::<String>()?)?,
+        median_nanos: None,
+        samples: None,
+        answer_hash: None,
+        memory: None,
+    })
+}
 
-    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
-        let json = value
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("Expected timing to be a JSON object.")?;
-
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
-
-        Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
-        })
-    }
+/// Version 2 and later store an object per part. `median_nanos` is missing before version 3.
+fn part_from_v2(value: &JsonValue) -> Option<PartTiming> {
+    let json = value.get::<HashMap<String, JsonValue>>()?;
+
+    let optional = |key: &str| match json.get(key) {
+        None => Some(None),
+        Some(v) if v.is_null() => Some(None),
+        Some(v) => Some(Some(v)),
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = |key: &str| match optional(key)? {
+        None => Some(None),
+        Some(v) => Some(Some(v.get::<f64>().copied().filter(|x| *x >= 0.0)? as u64)),
+    };
+
+    let answer_hash = match optional("answer_hash")? {
+        None => None,
+        Some(v) => Some(u64::from_str_radix(v.get::<String>()?, 16).ok()?),
+    };
+
+    Some(PartTiming {
+        nanos: json.get("nanos")?.get::<f64>().copied()?,
+        median_nanos: match optional("median_nanos")? {
+            None => None,
+            Some(v) => Some(v.get::<f64>().copied()?),
+        },
+        samples: count("samples")?,
+        answer_hash,
+        memory: match optional("memory")? {
+            None => None,
+            Some(v) => Some(memory_from_json(v)?),
+        },
+    })
+}
+
+fn memory_from_json(value: &JsonValue) -> Option<MemoryStats> {
+    let json = value.get::<HashMap<String, JsonValue>>()?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = |key: &str| {
+        json.get(key)?
+            .get::<f64>()
+            .copied()
+            .filter(|x| *x >= 0.0)
+            .map(|x| x as u64)
+    };
+
+    Some(MemoryStats {
+        allocations: count("allocations")?,
+        total_bytes: count("total_bytes")?,
+        peak_bytes: count("peak_bytes")?,
+    })
+}
+
+fn timing_from_json(
+    value: &JsonValue,
+    parse_part: fn(&JsonValue) -> Option<PartTiming>,
+) -> Result<Timing, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?;
+
+    let day = json
+        .get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.")?;
+
+    let part = |key: &str| {
+        json.get(key)
+            .and_then(|v| if v.is_null() { Some(None) } else { parse_part(v).map(Some) })
+            .ok_or(format!("Expected timing.{key} to be null or a part timing."))
+    };
+
+    let part_1 = part("part_1")?;
+    let part_2 = part("part_2")?;
+
+    let total_nanos = json
+        .get("total_nanos")
+        .and_then(|v| v.get::<f64>().copied())
+        .ok_or("Expected timing.total_nanos to be a number.")?;
+
+    Ok(Timing {
+        day,
+        part_1,
+        part_2,
+        total_nanos,
+    })
 }
 
 /* -------------------------------------------------------------------------- */
@@ -181,26 +386,36 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    fn part(nanos: f64) -> PartTiming {
+        PartTiming {
+            nanos,
+            median_nanos: Some(nanos),
+            samples: Some(10),
+            answer_hash: None,
+            memory: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(part(10e6)),
+                    part_2: Some(part(20e6)),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(part(30e6)),
+                    part_2: Some(part(40e6)),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(part(40e6)),
                     part_2: None,
                     total_nanos: 4e+10,
                 },
@@ -218,11 +433,51 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.samples, None);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_versioned_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "03", "part_1": { "nanos": 74, "samples": 100000, "answer_hash": "00000000000000ff" }, "part_2": { "nanos": 2.5e6, "samples": null }, "total_nanos": 2500074 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(3));
+
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!((part_1.nanos, part_1.samples, part_1.answer_hash), (74.0, Some(100_000), Some(255)));
+
+            let part_2 = timing.part_2.as_ref().unwrap();
+            assert_eq!((part_2.nanos, part_2.samples, part_2.answer_hash), (2_500_000.0, None, None));
+            assert_eq!((part_1.median_nanos, part_2.median_nanos), (None, None));
+        }
+
+        #[test]
+        fn handles_medians() {
+            let json = r#"{ "version": 3, "data": [{ "day": "03", "part_1": { "nanos": 80, "median_nanos": 74, "samples": 10 }, "part_2": { "nanos": 5, "median_nanos": null, "samples": null }, "total_nanos": 85 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+
+            assert_eq!(timing.part_1.as_ref().unwrap().median_nanos, Some(74.0));
+            assert_eq!(timing.part_2.as_ref().unwrap().median_nanos, None);
+        }
+
+        #[test]
+        fn rejects_unknown_versions() {
+            let json = r#"{ "version": 4, "data": [] }"#.to_string();
+            assert_eq!(Timings::try_from(json).unwrap_err(), "unsupported timings version 4.");
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_string_parts_in_current_version() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,6 +502,8 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::runner::MemoryStats;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,9 +523,29 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1.as_mut().unwrap().answer_hash = Some(u64::MAX);
+            timings.data[1].part_2.as_mut().unwrap().memory = Some(MemoryStats {
+                allocations: 12,
+                total_bytes: 4096,
+                peak_bytes: 1024,
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            assert_eq!(parsed.data.len(), 3);
+            for (a, b) in timings.data.iter().zip(&parsed.data) {
+                assert_eq!((a.day, &a.part_1, &a.part_2), (b.day, &b.part_1, &b.part_2));
+            }
+        }
     }
 
     mod is_day_complete {
+        use super::part;
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -279,13 +556,13 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(part(1e6)),
+                    part_2: Some(part(2e6)),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +570,13 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(part(1e6)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -313,7 +590,7 @@ mod tests {
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -0,0 +1,182 @@
+/// Polls the files a day depends on, for `cargo solve --watch`.
+/// Polling keeps this free of platform specific APIs; a snapshot of a few dozen files is cheap to take.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::Day;
+use crate::template::examples::is_example_of;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Changes are picked up once no further change happened for this long, so that an editor saving several files
+/// triggers a single run.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+pub struct Watcher {
+    root: PathBuf,
+    day: Day,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(root: impl Into<PathBuf>, day: Day) -> Self {
+        let root = root.into();
+        let snapshot = snapshot(&root, day);
+        Self {
+            root,
+            day,
+            snapshot,
+        }
+    }
+
+    /// Whether a watched file was modified, created or removed since the last call.
+    pub fn poll(&mut self) -> bool {
+        let next = snapshot(&self.root, self.day);
+        let changed = next != self.snapshot;
+        self.snapshot = next;
+        changed
+    }
+
+    /// Block until a watched file changed and then settled.
+    pub fn wait(&mut self) {
+        while !self.poll() {
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        loop {
+            thread::sleep(DEBOUNCE);
+            if !self.poll() {
+                break;
+            }
+        }
+    }
+}
+
+/// The solution of `day`, the library modules and the day's input and example files.
+pub fn watched_files(root: &Path, day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        root.join("src").join("bin").join(format!("{day}.rs")),
+        root.join("data").join("inputs").join(format!("{day}.txt")),
+    ];
+
+    collect_modules(&root.join("src"), &mut files);
+
+    if let Ok(entries) = fs::read_dir(root.join("data").join("examples")) {
+        files.extend(entries.filter_map(Result::ok).map(|e| e.path()).filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| is_example_of(name, day))
+        }));
+    }
+
+    files.sort();
+    files
+}
+
+/// All `.rs` files below `dir`, except for the other solution binaries in `src/bin`.
+fn collect_modules(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != "bin") {
+                collect_modules(&path, files);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Modification times of all watched files that exist.
+fn snapshot(root: &Path, day: Day) -> Snapshot {
+    watched_files(root, day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Watcher, watched_files};
+    use crate::day;
+    use std::{env, fs, path::PathBuf};
+
+    fn fixture(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-watch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for dir in ["src/bin", "src/template", "data/inputs", "data/examples"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "src/lib.rs",
+            "src/template/mod.rs",
+            "src/bin/03.rs",
+            "src/bin/04.rs",
+            "data/inputs/03.txt",
+            "data/examples/03.txt",
+            "data/examples/04.txt",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        root
+    }
+
+    #[test]
+    fn lists_watched_files() {
+        let root = fixture("list");
+
+        let files: Vec<String> = watched_files(&root, day!(3))
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        assert_eq!(
+            files,
+            [
+                "data/examples/03.txt",
+                "data/inputs/03.txt",
+                "src/bin/03.rs",
+                "src/lib.rs",
+                "src/template/mod.rs",
+            ]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn detects_created_and_removed_files() {
+        let root = fixture("poll");
+        let mut watcher = Watcher::new(&root, day!(3));
+        assert!(!watcher.poll());
+
+        fs::write(root.join("data/examples/04-2.txt"), "").unwrap();
+        assert!(!watcher.poll());
+
+        fs::write(root.join("data/examples/03-2.txt"), "").unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        fs::remove_file(root.join("src/lib.rs")).unwrap();
+        assert!(watcher.poll());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,13 @@
+//! Small xorshift generator, so the property tests are reproducible without extra dependencies.
+
+pub struct Rng(pub u64);
+
+impl Rng {
+    /// The next value in `0..bound`.
+    pub fn next(&mut self, bound: i64) -> i64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as i64
+    }
+}
@@ -0,0 +1,176 @@
+//! Fixed-width text blocks whose fields are laid out in columns, like a worksheet of stacked numbers.
+
+use std::ops::Range;
+
+/// Order in which the cells of a [`ColumnGroup`] are read into strings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Orientation {
+    /// One string per row.
+    Rows,
+    /// One string per column, read top to bottom.
+    Columns,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+/// A block of text padded with blanks to a rectangle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextBlock {
+    cells: Vec<Vec<char>>,
+    width: usize,
+}
+
+/// The cells of a [`TextBlock`] within a range of rows and columns.
+#[derive(Debug, Clone)]
+pub struct ColumnGroup<'a> {
+    block: &'a TextBlock,
+    rows: Range<usize>,
+    columns: Range<usize>,
+}
+
+impl TextBlock {
+    /// Builds a block from the non-empty lines of `text`.
+    pub fn new(text: &str) -> Self {
+        let cells: Vec<Vec<char>> = text
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+        let width = cells.iter().map(Vec::len).max().unwrap_or(0);
+
+        Self { cells, width }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The character at `(row, column)`, blank if the line is shorter.
+    pub fn get(&self, row: usize, column: usize) -> char {
+        self.cells[row].get(column).copied().unwrap_or(' ')
+    }
+
+    fn is_blank_column(&self, column: usize) -> bool {
+        (0..self.height()).all(|row| self.get(row, column).is_whitespace())
+    }
+
+    /// Splits the block into groups of columns separated by columns that are blank in every row.
+    pub fn column_groups(&self) -> Vec<ColumnGroup<'_>> {
+        let mut groups = vec![];
+        let mut start = None;
+
+        for column in 0..=self.width {
+            let blank = column == self.width || self.is_blank_column(column);
+            match (start, blank) {
+                (None, false) => start = Some(column),
+                (Some(from), true) => {
+                    groups.push(ColumnGroup {
+                        block: self,
+                        rows: 0..self.height(),
+                        columns: from..column,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        groups
+    }
+}
+
+impl<'a> ColumnGroup<'a> {
+    pub fn rows(&self) -> Range<usize> {
+        self.rows.clone()
+    }
+
+    pub fn columns(&self) -> Range<usize> {
+        self.columns.clone()
+    }
+
+    /// The same columns restricted to `rows`, which are indices into the whole block.
+    pub fn with_rows(&self, rows: Range<usize>) -> ColumnGroup<'a> {
+        ColumnGroup {
+            block: self.block,
+            rows,
+            columns: self.columns.clone(),
+        }
+    }
+
+    /// Reads the group into strings with all blanks removed, dropping the ones that end up empty.
+    ///
+    /// `direction` orders the columns: for [`Orientation::Rows`] it is the order of characters within each string,
+    /// for [`Orientation::Columns`] the order of the strings.
+    pub fn read(&self, orientation: Orientation, direction: Direction) -> Vec<String> {
+        let columns: Vec<usize> = match direction {
+            Direction::LeftToRight => self.columns.clone().collect(),
+            Direction::RightToLeft => self.columns.clone().rev().collect(),
+        };
+
+        let text = |cells: &mut dyn Iterator<Item = char>| -> String {
+            cells.filter(|c| !c.is_whitespace()).collect()
+        };
+
+        let strings: Vec<String> = match orientation {
+            Orientation::Rows => self
+                .rows
+                .clone()
+                .map(|row| text(&mut columns.iter().map(|&column| self.block.get(row, column))))
+                .collect(),
+            Orientation::Columns => columns
+                .iter()
+                .map(|&column| text(&mut self.rows.clone().map(|row| self.block.get(row, column))))
+                .collect(),
+        };
+
+        strings.into_iter().filter(|s| !s.is_empty()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSHEET: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
+    #[test]
+    fn test_column_groups() {
+        let block = TextBlock::new(WORKSHEET);
+        assert_eq!(block.width(), 15);
+        assert_eq!(block.height(), 4);
+
+        let columns: Vec<Range<usize>> = block.column_groups().iter().map(ColumnGroup::columns).collect();
+        assert_eq!(columns, vec![0..3, 4..7, 8..11, 12..15]);
+    }
+
+    #[test]
+    fn test_ragged_lines() {
+        let block = TextBlock::new("ab  c\nd\n\n   ef\n");
+        assert_eq!(block.height(), 3);
+        assert_eq!(block.get(1, 4), ' ');
+
+        let columns: Vec<Range<usize>> = block.column_groups().iter().map(ColumnGroup::columns).collect();
+        assert_eq!(columns, vec![0..2, 3..5]);
+    }
+
+    #[test]
+    fn test_read() {
+        let block = TextBlock::new(WORKSHEET);
+        let groups = block.column_groups();
+        let numbers = groups[0].with_rows(0..3);
+
+        assert_eq!(numbers.read(Orientation::Rows, Direction::LeftToRight), vec!["123", "45", "6"]);
+        assert_eq!(numbers.read(Orientation::Rows, Direction::RightToLeft), vec!["321", "54", "6"]);
+        assert_eq!(numbers.read(Orientation::Columns, Direction::LeftToRight), vec!["1", "24", "356"]);
+        assert_eq!(groups[3].with_rows(0..3).read(Orientation::Columns, Direction::RightToLeft), vec!["4", "431", "623"]);
+        assert_eq!(groups[1].with_rows(3..4).read(Orientation::Rows, Direction::LeftToRight), vec!["+"]);
+    }
+}